@@ -0,0 +1,267 @@
+//! Conversions between slices of timestamps/monotonic times and
+//! [Apache Arrow](https://crates.io/crates/arrow-array) arrays
+//!
+//! Timestamps are converted to `Timestamp*Array` with the `UTC` time zone, monotonic times to
+//! `Duration*Array`. Conversions to second, millisecond and microsecond arrays apply the given
+//! [`Rounding`] policy, conversions to nanosecond arrays fail for values after 2262-04-11.
+//! Timestamp arrays are read regardless of their time zone, as Arrow stores all timestamps since
+//! the UNIX epoch. Negative values can not be read.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bma_ts::{Rounding, Timestamp, arrow};
+//! use arrow_array::types::{TimestampMicrosecondType, TimestampNanosecondType};
+//!
+//! let values = [Timestamp::from_nanos(1_500), Timestamp::from_nanos(2_500)];
+//! let array = arrow::timestamp_array::<TimestampNanosecondType>(&values, Rounding::Exact)
+//!     .unwrap();
+//! assert_eq!(arrow::timestamps_from_array(&array).unwrap(), [Some(values[0]), Some(values[1])]);
+//! let array = arrow::timestamp_array::<TimestampMicrosecondType>(&values, Rounding::Nearest)
+//!     .unwrap();
+//! assert_eq!(array.values(), &[2, 3]);
+//! ```
+use std::time::Duration;
+
+use arrow_array::types::{
+    ArrowPrimitiveType, ArrowTimestampType, DurationMicrosecondType, DurationMillisecondType,
+    DurationNanosecondType, DurationSecondType,
+};
+use arrow_array::{Array, PrimitiveArray};
+use arrow_schema::TimeUnit;
+
+use crate::{EpochUnit, Error, Monotonic, Rounding, Timestamp};
+
+/// Time zone set for timestamp arrays
+pub const TIME_ZONE: &str = "UTC";
+
+/// Arrow duration types
+pub trait ArrowDurationType: ArrowPrimitiveType<Native = i64> {
+    const UNIT: TimeUnit;
+}
+
+impl ArrowDurationType for DurationSecondType {
+    const UNIT: TimeUnit = TimeUnit::Second;
+}
+impl ArrowDurationType for DurationMillisecondType {
+    const UNIT: TimeUnit = TimeUnit::Millisecond;
+}
+impl ArrowDurationType for DurationMicrosecondType {
+    const UNIT: TimeUnit = TimeUnit::Microsecond;
+}
+impl ArrowDurationType for DurationNanosecondType {
+    const UNIT: TimeUnit = TimeUnit::Nanosecond;
+}
+
+fn epoch_unit(unit: TimeUnit) -> EpochUnit {
+    match unit {
+        TimeUnit::Second => EpochUnit::Secs,
+        TimeUnit::Millisecond => EpochUnit::Millis,
+        TimeUnit::Microsecond => EpochUnit::Micros,
+        TimeUnit::Nanosecond => EpochUnit::Nanos,
+    }
+}
+
+fn to_native(value: Duration, unit: EpochUnit, rounding: Rounding) -> Result<i64, Error> {
+    Ok(unit.to_units(value, rounding)?.try_into()?)
+}
+
+fn from_native(value: i64, unit: EpochUnit) -> Result<Duration, Error> {
+    let value = u64::try_from(value)
+        .map_err(|_| Error::Convert(format!("negative Arrow time {} is not supported", value)))?;
+    Ok(unit.timestamp(value).0)
+}
+
+fn to_array<T, V>(
+    values: &[V],
+    unit: TimeUnit,
+    rounding: Rounding,
+) -> Result<PrimitiveArray<T>, Error>
+where
+    T: ArrowPrimitiveType<Native = i64>,
+    V: Copy + Into<Duration>,
+{
+    let unit = epoch_unit(unit);
+    let values = values
+        .iter()
+        .map(|v| to_native((*v).into(), unit, rounding))
+        .collect::<Result<Vec<i64>, Error>>()?;
+    Ok(PrimitiveArray::from_iter_values(values))
+}
+
+fn to_array_opt<T, V>(
+    values: &[Option<V>],
+    unit: TimeUnit,
+    rounding: Rounding,
+) -> Result<PrimitiveArray<T>, Error>
+where
+    T: ArrowPrimitiveType<Native = i64>,
+    V: Copy + Into<Duration>,
+{
+    let unit = epoch_unit(unit);
+    values
+        .iter()
+        .map(|v| v.map(|v| to_native(v.into(), unit, rounding)).transpose())
+        .collect()
+}
+
+fn from_array<T, V>(array: &PrimitiveArray<T>, unit: TimeUnit) -> Result<Vec<Option<V>>, Error>
+where
+    T: ArrowPrimitiveType<Native = i64>,
+    V: From<Duration>,
+{
+    let unit = epoch_unit(unit);
+    array
+        .iter()
+        .map(|v| v.map(|v| from_native(v, unit).map(Into::into)).transpose())
+        .collect()
+}
+
+fn from_array_non_null<T, V>(array: &PrimitiveArray<T>, unit: TimeUnit) -> Result<Vec<V>, Error>
+where
+    T: ArrowPrimitiveType<Native = i64>,
+    V: From<Duration>,
+{
+    if array.null_count() > 0 {
+        return Err(Error::Convert(format!(
+            "Arrow array contains {} null(s)",
+            array.null_count()
+        )));
+    }
+    let unit = epoch_unit(unit);
+    array
+        .values()
+        .iter()
+        .map(|v| from_native(*v, unit).map(Into::into))
+        .collect()
+}
+
+// Timestamp
+
+/// Builds a timestamp array with the `UTC` time zone
+pub fn timestamp_array<T: ArrowTimestampType>(
+    values: &[Timestamp],
+    rounding: Rounding,
+) -> Result<PrimitiveArray<T>, Error> {
+    Ok(to_array::<T, _>(values, T::UNIT, rounding)?.with_timezone(TIME_ZONE))
+}
+
+/// Builds a timestamp array with the `UTC` time zone, `None` values become nulls
+pub fn timestamp_array_opt<T: ArrowTimestampType>(
+    values: &[Option<Timestamp>],
+    rounding: Rounding,
+) -> Result<PrimitiveArray<T>, Error> {
+    Ok(to_array_opt::<T, _>(values, T::UNIT, rounding)?.with_timezone(TIME_ZONE))
+}
+
+/// Reads a timestamp array, nulls become `None` values
+pub fn timestamps_from_array<T: ArrowTimestampType>(
+    array: &PrimitiveArray<T>,
+) -> Result<Vec<Option<Timestamp>>, Error> {
+    from_array(array, T::UNIT)
+}
+
+/// Reads a timestamp array, fails if the array contains nulls
+pub fn timestamps_from_array_non_null<T: ArrowTimestampType>(
+    array: &PrimitiveArray<T>,
+) -> Result<Vec<Timestamp>, Error> {
+    from_array_non_null(array, T::UNIT)
+}
+
+// Monotonic
+
+/// Builds a duration array
+pub fn monotonic_array<T: ArrowDurationType>(
+    values: &[Monotonic],
+    rounding: Rounding,
+) -> Result<PrimitiveArray<T>, Error> {
+    to_array(values, T::UNIT, rounding)
+}
+
+/// Builds a duration array, `None` values become nulls
+pub fn monotonic_array_opt<T: ArrowDurationType>(
+    values: &[Option<Monotonic>],
+    rounding: Rounding,
+) -> Result<PrimitiveArray<T>, Error> {
+    to_array_opt(values, T::UNIT, rounding)
+}
+
+/// Reads a duration array, nulls become `None` values
+pub fn monotonics_from_array<T: ArrowDurationType>(
+    array: &PrimitiveArray<T>,
+) -> Result<Vec<Option<Monotonic>>, Error> {
+    from_array(array, T::UNIT)
+}
+
+/// Reads a duration array, fails if the array contains nulls
+pub fn monotonics_from_array_non_null<T: ArrowDurationType>(
+    array: &PrimitiveArray<T>,
+) -> Result<Vec<Monotonic>, Error> {
+    from_array_non_null(array, T::UNIT)
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::TimestampNanosecondArray;
+    use arrow_array::types::{
+        DurationMillisecondType, DurationNanosecondType, TimestampMillisecondType,
+        TimestampNanosecondType, TimestampSecondType,
+    };
+    use arrow_schema::{DataType, TimeUnit};
+
+    use super::*;
+
+    #[test]
+    fn test_arrow_timestamps() {
+        let values = [
+            Timestamp::from_nanos(1_713_027_747_365_000_001),
+            Timestamp::from_secs(1_713_027_747),
+        ];
+        let array = timestamp_array::<TimestampNanosecondType>(&values, Rounding::Exact).unwrap();
+        assert_eq!(
+            array.data_type(),
+            &DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into()))
+        );
+        assert_eq!(timestamps_from_array_non_null(&array).unwrap(), values);
+        assert!(timestamp_array::<TimestampMillisecondType>(&values, Rounding::Exact).is_err());
+        let array = timestamp_array::<TimestampSecondType>(&values, Rounding::Floor).unwrap();
+        assert_eq!(array.values(), &[1_713_027_747, 1_713_027_747]);
+        assert!(
+            timestamp_array::<TimestampNanosecondType>(
+                &[Timestamp::from_secs(10_000_000_000)],
+                Rounding::Floor
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_arrow_nulls() {
+        let values = [None, Some(Timestamp::from_millis(1_713_027_747_365))];
+        let array =
+            timestamp_array_opt::<TimestampMillisecondType>(&values, Rounding::Exact).unwrap();
+        assert_eq!(array.null_count(), 1);
+        assert_eq!(timestamps_from_array(&array).unwrap(), values);
+        assert!(timestamps_from_array_non_null(&array).is_err());
+        let array = TimestampNanosecondArray::from(vec![-1]);
+        assert!(timestamps_from_array(&array).is_err());
+    }
+
+    #[test]
+    fn test_arrow_durations() {
+        let values = [Monotonic::from_nanos(1_500_000), Monotonic::from_secs(2)];
+        let array = monotonic_array::<DurationMillisecondType>(&values, Rounding::Nearest).unwrap();
+        assert_eq!(array.values(), &[2, 2_000]);
+        let array = monotonic_array::<DurationNanosecondType>(&values, Rounding::Exact).unwrap();
+        assert_eq!(monotonics_from_array_non_null(&array).unwrap(), values);
+        let array = monotonic_array_opt::<DurationNanosecondType>(
+            &[None, Some(values[0])],
+            Rounding::Exact,
+        )
+        .unwrap();
+        assert_eq!(
+            monotonics_from_array(&array).unwrap(),
+            [None, Some(values[0])]
+        );
+    }
+}
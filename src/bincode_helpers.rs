@@ -0,0 +1,214 @@
+//! Alternative bincode encodings
+//!
+//! The wrappers encode the inner [`Timestamp`](crate::Timestamp)/[`Monotonic`](crate::Monotonic)
+//! (or any other `Duration`-backed value) in a fixed format, regardless of the `as-float-secs`
+//! feature, so different on-disk formats may coexist in one binary:
+//!
+//! | wrapper       | format                                                       |
+//! |---------------|--------------------------------------------------------------|
+//! | [`Nanos`]     | `u64` nanoseconds, the integer encoding of the configuration |
+//! | [`FixedLe`]   | `u64` nanoseconds, 8 bytes little-endian                     |
+//! | [`FixedBe`]   | `u64` nanoseconds, 8 bytes big-endian                        |
+//! | [`FullRange`] | `u64` seconds and `u32` nanoseconds                          |
+//! | [`FloatSecs`] | `f64` seconds                                                |
+use std::time::Duration;
+
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{BorrowDecode, Decode, Encode};
+
+use crate::common::{NANOS_PER_SEC, nanos_u64};
+
+fn encode_nanos(value: Duration) -> Result<u64, EncodeError> {
+    nanos_u64(value.as_nanos()).map_err(|e| EncodeError::OtherString(e.to_string()))
+}
+
+macro_rules! impl_borrow_decode {
+    ($w: ident) => {
+        impl<'de, C, T: From<Duration>> BorrowDecode<'de, C> for $w<T> {
+            fn borrow_decode<D: BorrowDecoder<'de, Context = C>>(
+                decoder: &mut D,
+            ) -> Result<Self, DecodeError> {
+                Self::decode(decoder)
+            }
+        }
+    };
+}
+
+/// Encodes the inner value as `u64` seconds and `u32` sub-second nanoseconds (see
+/// [full range](crate#full-range))
+///
+/// # Example
+///
+/// ```rust
+/// use bma_ts::{Monotonic, bincode_helpers::FullRange};
+///
+/// let config = bincode::config::standard();
+/// let mono = Monotonic::from_secs(u64::MAX);
+/// assert!(bincode::encode_to_vec(mono, config).is_err());
+/// let encoded = bincode::encode_to_vec(FullRange(mono), config).unwrap();
+/// let (decoded, _): (FullRange<Monotonic>, _) =
+///     bincode::decode_from_slice(&encoded, config).unwrap();
+/// assert_eq!(decoded.0, mono);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct FullRange<T>(pub T);
+
+impl<T: AsRef<Duration>> Encode for FullRange<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let d = self.0.as_ref();
+        d.as_secs().encode(encoder)?;
+        d.subsec_nanos().encode(encoder)
+    }
+}
+
+impl<C, T: From<Duration>> Decode<C> for FullRange<T> {
+    fn decode<D: Decoder<Context = C>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let secs = u64::decode(decoder)?;
+        let nanos = u32::decode(decoder)?;
+        if nanos >= NANOS_PER_SEC {
+            return Err(DecodeError::OtherString(format!(
+                "nanoseconds {} out of range",
+                nanos
+            )));
+        }
+        Ok(Self(Duration::new(secs, nanos).into()))
+    }
+}
+
+impl_borrow_decode!(FullRange);
+
+/// Encodes the inner value as `u64` nanoseconds, using the integer encoding of the
+/// configuration (variable or fixed)
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Nanos<T>(pub T);
+
+impl<T: AsRef<Duration>> Encode for Nanos<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encode_nanos(*self.0.as_ref())?.encode(encoder)
+    }
+}
+
+impl<C, T: From<Duration>> Decode<C> for Nanos<T> {
+    fn decode<D: Decoder<Context = C>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self(Duration::from_nanos(u64::decode(decoder)?).into()))
+    }
+}
+
+impl_borrow_decode!(Nanos);
+
+/// Encodes the inner value as `u64` nanoseconds, 8 bytes little-endian regardless of the
+/// configuration
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct FixedLe<T>(pub T);
+
+impl<T: AsRef<Duration>> Encode for FixedLe<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encode_nanos(*self.0.as_ref())?
+            .to_le_bytes()
+            .encode(encoder)
+    }
+}
+
+impl<C, T: From<Duration>> Decode<C> for FixedLe<T> {
+    fn decode<D: Decoder<Context = C>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let nanos = u64::from_le_bytes(<[u8; 8]>::decode(decoder)?);
+        Ok(Self(Duration::from_nanos(nanos).into()))
+    }
+}
+
+impl_borrow_decode!(FixedLe);
+
+/// Encodes the inner value as `u64` nanoseconds, 8 bytes big-endian regardless of the
+/// configuration
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct FixedBe<T>(pub T);
+
+impl<T: AsRef<Duration>> Encode for FixedBe<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encode_nanos(*self.0.as_ref())?
+            .to_be_bytes()
+            .encode(encoder)
+    }
+}
+
+impl<C, T: From<Duration>> Decode<C> for FixedBe<T> {
+    fn decode<D: Decoder<Context = C>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let nanos = u64::from_be_bytes(<[u8; 8]>::decode(decoder)?);
+        Ok(Self(Duration::from_nanos(nanos).into()))
+    }
+}
+
+impl_borrow_decode!(FixedBe);
+
+/// Encodes the inner value as `f64` seconds (see [float seconds](crate#float-seconds))
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct FloatSecs<T>(pub T);
+
+impl<T: AsRef<Duration>> Encode for FloatSecs<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.as_ref().as_secs_f64().encode(encoder)
+    }
+}
+
+impl<C, T: From<Duration>> Decode<C> for FloatSecs<T> {
+    fn decode<D: Decoder<Context = C>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let secs = f64::decode(decoder)?;
+        Duration::try_from_secs_f64(secs)
+            .map(|d| Self(d.into()))
+            .map_err(|e| DecodeError::OtherString(format!("invalid seconds {}: {}", secs, e)))
+    }
+}
+
+impl_borrow_decode!(FloatSecs);
+
+#[cfg(test)]
+mod tests {
+    use super::{FixedBe, FixedLe, FloatSecs, FullRange, Nanos};
+    use crate::{Monotonic, Timestamp};
+
+    #[test]
+    fn test_full_range() {
+        let config = bincode::config::standard().with_fixed_int_encoding();
+        let ts = Timestamp::from_nanos(1_713_027_747_000_000_365);
+        let encoded = bincode::encode_to_vec(FullRange(ts), config).unwrap();
+        assert_eq!(encoded.len(), 12);
+        let (decoded, _): (FullRange<Timestamp>, _) =
+            bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded.0, ts);
+        let encoded = bincode::encode_to_vec((5_u64, 1_000_000_000_u32), config).unwrap();
+        assert!(bincode::decode_from_slice::<FullRange<Monotonic>, _>(&encoded, config).is_err());
+    }
+
+    #[test]
+    fn test_fixed() {
+        let config = bincode::config::standard();
+        let ts = Timestamp::from_nanos(1_713_027_747_000_000_365);
+        let value = (
+            Nanos(ts),
+            FixedLe(ts),
+            FixedBe(ts),
+            FullRange(ts),
+            FloatSecs(Timestamp::from_millis(1_500)),
+        );
+        let encoded = bincode::encode_to_vec(value, config).unwrap();
+        assert_eq!(
+            encoded[9..25],
+            [
+                1_713_027_747_000_000_365_u64.to_le_bytes(),
+                1_713_027_747_000_000_365_u64.to_be_bytes()
+            ]
+            .concat()
+        );
+        let (decoded, len): (
+            (Nanos<_>, FixedLe<_>, FixedBe<_>, FullRange<_>, FloatSecs<_>),
+            _,
+        ) = bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(len, encoded.len());
+        assert_eq!(decoded, value);
+        assert!(bincode::encode_to_vec(FixedLe(Monotonic::from_secs(u64::MAX)), config).is_err());
+        let encoded = bincode::encode_to_vec(-1.0_f64, config).unwrap();
+        assert!(bincode::decode_from_slice::<FloatSecs<Monotonic>, _>(&encoded, config).is_err());
+    }
+}
@@ -0,0 +1,255 @@
+//! CBOR date/time tags (RFC 8949) support for [ciborium](https://crates.io/crates/ciborium)
+//!
+//! The module can be used with `#[serde(with = "bma_ts::cbor")]` and serializes timestamps with
+//! tag 1 (epoch seconds): as an integer if there are no sub-second nanoseconds, as a float
+//! otherwise (see [float seconds](crate#float-seconds)). Use [`rfc3339`] to serialize timestamps
+//! with tag 0 (RFC 3339 text).
+//!
+//! Both modules deserialize tag 1, tag 0 and untagged values (numbers are epoch seconds, strings
+//! are RFC 3339).
+//!
+//! # Example
+//!
+//! ```rust
+//! use bma_ts::Timestamp;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!     #[serde(with = "bma_ts::cbor")]
+//!     t: Timestamp,
+//! }
+//!
+//! let event = Event { t: Timestamp::from_secs(1_713_027_747) };
+//! let mut packed = Vec::new();
+//! ciborium::into_writer(&event, &mut packed).unwrap();
+//! let unpacked: Event = ciborium::from_reader(packed.as_slice()).unwrap();
+//! assert_eq!(unpacked.t, event.t);
+//! ```
+use core::fmt;
+use std::time::Duration;
+
+use ciborium::tag::{Captured, Required};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::{Timestamp, rfc3339 as rfc3339_fmt};
+
+/// Standard date/time string tag
+pub const TAG_DATE_TIME: u64 = 0;
+/// Epoch-based date/time tag
+pub const TAG_EPOCH: u64 = 1;
+
+pub fn serialize<S>(value: &Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if value.0.subsec_nanos() == 0 {
+        Required::<_, TAG_EPOCH>(value.0.as_secs()).serialize(serializer)
+    } else {
+        Required::<_, TAG_EPOCH>(value.0.as_secs_f64()).serialize(serializer)
+    }
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Captured(tag, item) = Captured::<Item>::deserialize(deserializer)?;
+    match (tag, item) {
+        (Some(TAG_EPOCH) | None, Item::Secs(secs)) => Ok(Timestamp::from_secs(secs)),
+        (Some(TAG_EPOCH) | None, Item::SecsF64(secs)) => Duration::try_from_secs_f64(secs)
+            .map(Into::into)
+            .map_err(de::Error::custom),
+        (Some(TAG_DATE_TIME) | None, Item::Text(s)) => rfc3339_fmt::parse(&s)
+            .map(Into::into)
+            .map_err(de::Error::custom),
+        (Some(tag), _) => Err(de::Error::custom(format_args!(
+            "invalid CBOR tag {} for the data item, expected {} (epoch) or {} (date/time string)",
+            tag, TAG_EPOCH, TAG_DATE_TIME
+        ))),
+    }
+}
+
+/// Serializes timestamps with CBOR tag 0 (RFC 3339 text, UTC, with 0, 3, 6 or 9 fraction digits)
+pub mod rfc3339 {
+    use ciborium::tag::Required;
+    use serde::{Deserializer, Serialize, Serializer};
+
+    use super::TAG_DATE_TIME;
+    use crate::{Timestamp, rfc3339};
+
+    pub fn serialize<S>(value: &Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let s = rfc3339::format(value.0).map_err(serde::ser::Error::custom)?;
+        Required::<_, TAG_DATE_TIME>(s).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize(deserializer)
+    }
+}
+
+enum Item {
+    Secs(u64),
+    SecsF64(f64),
+    Text(String),
+}
+
+impl<'de> Deserialize<'de> for Item {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ItemVisitor)
+    }
+}
+
+struct ItemVisitor;
+
+impl de::Visitor<'_> for ItemVisitor {
+    type Value = Item;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("epoch seconds or an RFC 3339 string")
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Item::Secs(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        u64::try_from(value).map(Item::Secs).map_err(|_| {
+            de::Error::custom(format_args!(
+                "epoch time {} is before the UNIX epoch",
+                value
+            ))
+        })
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Item::SecsF64(value))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Item::Text(value.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ciborium::Value;
+    use ciborium::tag::Required;
+    use serde::{Deserialize, Serialize};
+
+    use crate::Timestamp;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Event {
+        #[serde(with = "super")]
+        t: Timestamp,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TextEvent {
+        #[serde(with = "super::rfc3339")]
+        t: Timestamp,
+    }
+
+    fn pack<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut buf = Vec::new();
+        ciborium::into_writer(value, &mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_cbor_epoch() {
+        let event = Event {
+            t: Timestamp::from_secs(1_713_027_747),
+        };
+        let packed = pack(&event);
+        let value: Value = ciborium::from_reader(packed.as_slice()).unwrap();
+        assert_eq!(
+            value,
+            Value::Map(vec![(
+                Value::Text("t".to_owned()),
+                Value::Tag(1, Box::new(Value::Integer(1_713_027_747.into())))
+            )])
+        );
+        assert_eq!(
+            ciborium::from_reader::<Event, _>(packed.as_slice()).unwrap(),
+            event
+        );
+        let event = Event {
+            t: Timestamp::from_millis(1_713_027_747_500),
+        };
+        let unpacked: Event = ciborium::from_reader(pack(&event).as_slice()).unwrap();
+        assert_eq!(unpacked, event);
+    }
+
+    #[test]
+    fn test_cbor_date_time() {
+        let event = TextEvent {
+            t: Timestamp::from_nanos(1_713_027_747_365_000_001),
+        };
+        let packed = pack(&event);
+        let value: Value = ciborium::from_reader(packed.as_slice()).unwrap();
+        assert_eq!(
+            value,
+            Value::Map(vec![(
+                Value::Text("t".to_owned()),
+                Value::Tag(
+                    0,
+                    Box::new(Value::Text("2024-04-13T17:02:27.365000001Z".to_owned()))
+                )
+            )])
+        );
+        let unpacked: Event = ciborium::from_reader(packed.as_slice()).unwrap();
+        assert_eq!(unpacked.t, event.t);
+    }
+
+    #[test]
+    fn test_cbor_untagged() {
+        #[derive(Serialize)]
+        struct Raw<T> {
+            t: T,
+        }
+        let unpacked: Event = ciborium::from_reader(
+            pack(&Raw {
+                t: 1_713_027_747_u64,
+            })
+            .as_slice(),
+        )
+        .unwrap();
+        assert_eq!(unpacked.t, Timestamp::from_secs(1_713_027_747));
+        let unpacked: Event = ciborium::from_reader(
+            pack(&Raw {
+                t: "2024-04-13T17:02:27Z",
+            })
+            .as_slice(),
+        )
+        .unwrap();
+        assert_eq!(unpacked.t, Timestamp::from_secs(1_713_027_747));
+        assert!(ciborium::from_reader::<Event, _>(pack(&Raw { t: -1 }).as_slice()).is_err());
+        let wrong_tag = Raw {
+            t: Required::<_, 0>(1_713_027_747_u64),
+        };
+        assert!(ciborium::from_reader::<Event, _>(pack(&wrong_tag).as_slice()).is_err());
+    }
+}
@@ -1,7 +1,53 @@
-use crate::{Monotonic, Timestamp};
+use crate::{Error, Monotonic, Timestamp};
 use core::fmt;
 use std::time::Duration;
 
+pub(crate) const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Converts nanoseconds to `u64`, fails for times after 2554-07-21
+#[cfg(any(feature = "serde", feature = "bincode"))]
+pub(crate) fn nanos_u64(nanos: u128) -> Result<u64, Error> {
+    nanos
+        .try_into()
+        .map_err(|_| Error::Convert(format!("time {} nanoseconds does not fit into u64", nanos)))
+}
+
+pub(crate) fn duration_from_nanos(nanos: u128) -> Result<Duration, Error> {
+    let secs = u64::try_from(nanos / u128::from(NANOS_PER_SEC))
+        .map_err(|_| Error::Convert(format!("time {} nanoseconds is out of range", nanos)))?;
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(Duration::new(
+        secs,
+        (nanos % u128::from(NANOS_PER_SEC)) as u32,
+    ))
+}
+
+/// Zigzag mapping of signed integers to unsigned ones (0, -1, 1, -2... to 0, 1, 2, 3...)
+pub(crate) trait ZigZag: Sized {
+    type Unsigned;
+    fn zigzag(self) -> Self::Unsigned;
+    fn unzigzag(value: Self::Unsigned) -> Self;
+}
+
+macro_rules! impl_zigzag {
+    ($s: ty, $u: ty) => {
+        impl ZigZag for $s {
+            type Unsigned = $u;
+            #[allow(clippy::cast_sign_loss)]
+            fn zigzag(self) -> $u {
+                ((self << 1) ^ (self >> (<$s>::BITS - 1))) as $u
+            }
+            #[allow(clippy::cast_possible_wrap)]
+            fn unzigzag(value: $u) -> $s {
+                ((value >> 1) as $s) ^ -((value & 1) as $s)
+            }
+        }
+    };
+}
+
+impl_zigzag!(i64, u64);
+impl_zigzag!(i128, u128);
+
 macro_rules! impl_common {
     ($t: ty) => {
         impl $t {
@@ -0,0 +1,584 @@
+//! Delta-of-delta (Gorilla-style) compression of timestamp/monotonic time sequences
+//!
+//! Values are stored as nanoseconds. The first value of each checkpoint block is stored as-is,
+//! for the following ones the difference between the current and the previous deltas is
+//! bit-packed:
+//!
+//! | prefix | payload | delta-of-delta range |
+//! |--------|---------|----------------------|
+//! | `0`    | -       | 0                    |
+//! | `10`   | 14 bits | ±8µs                 |
+//! | `110`  | 20 bits | ±524µs               |
+//! | `1110` | 32 bits | ±2.1s                |
+//! | `1111` | 64 bits | any                  |
+//!
+//! A regularly sampled sequence takes about one bit per value, plus 16 bytes per checkpoint.
+//! Sequences are not required to be monotone, however jitter and backward steps cost more bits.
+//!
+//! Checkpoints allow random access to any value by decoding at most one block.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bma_ts::{Timestamp, compress::DeltaOfDelta};
+//!
+//! let mut seq = DeltaOfDelta::new();
+//! for i in 0..1_000 {
+//!     seq.push(Timestamp::from_secs(1_713_027_747 + i)).unwrap();
+//! }
+//! assert_eq!(seq.len(), 1_000);
+//! assert_eq!(seq.get(500), Some(Timestamp::from_secs(1_713_028_247)));
+//! assert!(seq.compressed_size() < 1_000);
+//! ```
+use std::marker::PhantomData;
+
+use crate::{Error, common::ZigZag};
+
+/// Default number of values per checkpoint block
+pub const DEFAULT_CHECKPOINT_INTERVAL: usize = 128;
+
+// payload bits for the prefixes 10, 110, 1110 and 1111
+const BUCKETS: [u32; 4] = [14, 20, 32, 64];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Checkpoint {
+    pos: u64,
+    value: u64,
+}
+
+/// Compressed sequence of timestamps or monotonic times
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeltaOfDelta<T> {
+    interval: usize,
+    len: usize,
+    bits: Vec<u8>,
+    bit_len: u64,
+    checkpoints: Vec<Checkpoint>,
+    last: u64,
+    last_delta: i64,
+    _t: PhantomData<T>,
+}
+
+impl<T> Default for DeltaOfDelta<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> DeltaOfDelta<T> {
+    /// Creates an empty sequence with the default checkpoint interval
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_checkpoint_interval(DEFAULT_CHECKPOINT_INTERVAL)
+    }
+    /// Creates an empty sequence with a checkpoint every `interval` values
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval is zero
+    pub fn with_checkpoint_interval(interval: usize) -> Self {
+        assert!(interval > 0, "checkpoint interval must be positive");
+        Self {
+            interval,
+            len: 0,
+            bits: Vec::new(),
+            bit_len: 0,
+            checkpoints: Vec::new(),
+            last: 0,
+            last_delta: 0,
+            _t: PhantomData,
+        }
+    }
+    #[inline]
+    pub fn checkpoint_interval(&self) -> usize {
+        self.interval
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Size of the packed data and checkpoints in bytes
+    pub fn compressed_size(&self) -> usize {
+        self.bits.len() + self.checkpoints.len() * 16
+    }
+    fn write_bits(&mut self, value: u64, count: u32) -> Result<(), Error> {
+        for i in (0..count).rev() {
+            let byte = usize::try_from(self.bit_len / 8)?;
+            if byte == self.bits.len() {
+                self.bits.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                self.bits[byte] |= 0x80 >> (self.bit_len % 8);
+            }
+            self.bit_len += 1;
+        }
+        Ok(())
+    }
+    fn write_dod(&mut self, dod: i64) -> Result<(), Error> {
+        if dod == 0 {
+            return self.write_bits(0, 1);
+        }
+        let z = dod.zigzag();
+        for (i, bits) in (1..).zip(BUCKETS) {
+            if bits == 64 || z < 1 << bits {
+                if bits == 64 {
+                    self.write_bits(0b1111, 4)?;
+                } else {
+                    self.write_bits(((1 << i) - 1) << 1, i + 1)?;
+                }
+                return self.write_bits(z, bits);
+            }
+        }
+        Ok(())
+    }
+    fn cursor(&self, index: usize) -> Cursor {
+        let cp = self.checkpoints[index / self.interval];
+        Cursor {
+            pos: cp.pos,
+            value: cp.value,
+            delta: 0,
+        }
+    }
+    #[cfg(any(feature = "serde", feature = "bincode"))]
+    fn from_parts(
+        interval: usize,
+        len: usize,
+        bits: Vec<u8>,
+        bit_len: u64,
+        checkpoints: Vec<(u64, u64)>,
+    ) -> Result<Self, Error> {
+        if interval == 0 {
+            return Err(Error::Decode("zero checkpoint interval".to_owned()));
+        }
+        if checkpoints.len() != len.div_ceil(interval) {
+            return Err(Error::Decode(format!(
+                "{} checkpoint(s) for {} value(s), {} expected",
+                checkpoints.len(),
+                len,
+                len.div_ceil(interval)
+            )));
+        }
+        if u64::try_from(bits.len())? != bit_len.div_ceil(8) {
+            return Err(Error::Decode(format!(
+                "{} byte(s) for {} bit(s)",
+                bits.len(),
+                bit_len
+            )));
+        }
+        // push ORs bits into the last byte, so the padding must be zero
+        if bit_len % 8 != 0
+            && bits
+                .last()
+                .is_some_and(|b| b & ((1 << (8 - bit_len % 8)) - 1) != 0)
+        {
+            return Err(Error::Decode("non-zero padding bits".to_owned()));
+        }
+        let mut seq = Self {
+            interval,
+            len,
+            bits,
+            bit_len,
+            checkpoints: checkpoints
+                .into_iter()
+                .map(|(pos, value)| Checkpoint { pos, value })
+                .collect(),
+            last: 0,
+            last_delta: 0,
+            _t: PhantomData,
+        };
+        let mut cursor = Cursor::default();
+        for index in 0..len {
+            if index % interval == 0 {
+                let next = seq.cursor(index);
+                if next.pos != cursor.pos {
+                    return Err(Error::Decode(format!(
+                        "checkpoint {} at bit {}, {} expected",
+                        index / interval,
+                        next.pos,
+                        cursor.pos
+                    )));
+                }
+                cursor = next;
+            } else {
+                cursor.step(&seq.bits, bit_len).ok_or_else(|| {
+                    Error::Decode(format!("packed data truncated at value {}", index))
+                })?;
+            }
+        }
+        if cursor.pos != bit_len {
+            return Err(Error::Decode(format!(
+                "{} trailing bit(s)",
+                bit_len - cursor.pos
+            )));
+        }
+        seq.last = cursor.value;
+        seq.last_delta = cursor.delta;
+        Ok(seq)
+    }
+}
+
+impl<T> DeltaOfDelta<T>
+where
+    T: From<u64> + TryInto<u64, Error = Error>,
+{
+    /// Appends a value, fails if the value does not fit into `u64` nanoseconds
+    pub fn push(&mut self, value: T) -> Result<(), Error> {
+        let nanos: u64 = value.try_into()?;
+        if self.len % self.interval == 0 {
+            self.checkpoints.push(Checkpoint {
+                pos: self.bit_len,
+                value: nanos,
+            });
+            self.last_delta = 0;
+        } else {
+            let delta = wrapping_delta(nanos, self.last);
+            self.write_dod(delta.wrapping_sub(self.last_delta))?;
+            self.last_delta = delta;
+        }
+        self.last = nanos;
+        self.len += 1;
+        Ok(())
+    }
+    /// Returns a value by its index, decodes the value checkpoint block up to the index
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        let mut cursor = self.cursor(index);
+        for _ in 0..index % self.interval {
+            cursor.step(&self.bits, self.bit_len)?;
+        }
+        Some(cursor.value.into())
+    }
+    /// Returns the last value
+    pub fn last(&self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(self.last.into())
+        }
+    }
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            seq: self,
+            index: 0,
+            cursor: Cursor::default(),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DeltaOfDelta<T>
+where
+    T: From<u64> + TryInto<u64, Error = Error>,
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Sequential decoder of [`DeltaOfDelta`] values
+pub struct Iter<'a, T> {
+    seq: &'a DeltaOfDelta<T>,
+    index: usize,
+    cursor: Cursor,
+}
+
+impl<T> Iterator for Iter<'_, T>
+where
+    T: From<u64>,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.seq.len {
+            return None;
+        }
+        if self.index % self.seq.interval == 0 {
+            self.cursor = self.seq.cursor(self.index);
+        } else {
+            self.cursor.step(&self.seq.bits, self.seq.bit_len)?;
+        }
+        self.index += 1;
+        Some(self.cursor.value.into())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.seq.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+#[derive(Default)]
+struct Cursor {
+    pos: u64,
+    value: u64,
+    delta: i64,
+}
+
+impl Cursor {
+    fn read_bits(&mut self, bits: &[u8], bit_len: u64, count: u32) -> Option<u64> {
+        if bit_len - self.pos < u64::from(count) {
+            return None;
+        }
+        let mut value = 0;
+        for _ in 0..count {
+            let byte = bits[usize::try_from(self.pos / 8).ok()?];
+            value = (value << 1) | u64::from((byte >> (7 - self.pos % 8)) & 1);
+            self.pos += 1;
+        }
+        Some(value)
+    }
+    fn step(&mut self, bits: &[u8], bit_len: u64) -> Option<()> {
+        let mut ones = 0;
+        while ones < BUCKETS.len() && self.read_bits(bits, bit_len, 1)? == 1 {
+            ones += 1;
+        }
+        if ones > 0 {
+            let dod = i64::unzigzag(self.read_bits(bits, bit_len, BUCKETS[ones - 1])?);
+            self.delta = self.delta.wrapping_add(dod);
+        }
+        self.value = self.value.wrapping_add_signed(self.delta);
+        Some(())
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn wrapping_delta(value: u64, prev: u64) -> i64 {
+    value.wrapping_sub(prev) as i64
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de, ser};
+
+    use super::DeltaOfDelta;
+
+    #[derive(Serialize)]
+    #[serde(rename = "DeltaOfDelta")]
+    struct RawRef<'a> {
+        interval: u64,
+        len: u64,
+        bit_len: u64,
+        bits: &'a [u8],
+        checkpoints: Vec<(u64, u64)>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "DeltaOfDelta")]
+    struct Raw {
+        interval: u64,
+        len: u64,
+        bit_len: u64,
+        bits: Vec<u8>,
+        checkpoints: Vec<(u64, u64)>,
+    }
+
+    impl<T> Serialize for DeltaOfDelta<T> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            RawRef {
+                interval: self.interval.try_into().map_err(ser::Error::custom)?,
+                len: self.len.try_into().map_err(ser::Error::custom)?,
+                bit_len: self.bit_len,
+                bits: &self.bits,
+                checkpoints: self.checkpoints.iter().map(|c| (c.pos, c.value)).collect(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, T> Deserialize<'de> for DeltaOfDelta<T> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let raw = Raw::deserialize(deserializer)?;
+            Self::from_parts(
+                raw.interval.try_into().map_err(de::Error::custom)?,
+                raw.len.try_into().map_err(de::Error::custom)?,
+                raw.bits,
+                raw.bit_len,
+                raw.checkpoints,
+            )
+            .map_err(de::Error::custom)
+        }
+    }
+}
+
+#[cfg(feature = "bincode")]
+mod bincode_impl {
+    use bincode::error::{DecodeError, EncodeError};
+    use bincode::{Decode, Encode};
+
+    use super::DeltaOfDelta;
+
+    impl<T> Encode for DeltaOfDelta<T> {
+        fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            u64::try_from(self.interval)
+                .map_err(|e| EncodeError::OtherString(e.to_string()))?
+                .encode(encoder)?;
+            u64::try_from(self.len)
+                .map_err(|e| EncodeError::OtherString(e.to_string()))?
+                .encode(encoder)?;
+            self.bit_len.encode(encoder)?;
+            self.bits.encode(encoder)?;
+            u64::try_from(self.checkpoints.len())
+                .map_err(|e| EncodeError::OtherString(e.to_string()))?
+                .encode(encoder)?;
+            for c in &self.checkpoints {
+                c.pos.encode(encoder)?;
+                c.value.encode(encoder)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<C, T> Decode<C> for DeltaOfDelta<T> {
+        fn decode<D: bincode::de::Decoder<Context = C>>(
+            decoder: &mut D,
+        ) -> Result<Self, DecodeError> {
+            let interval = u64::decode(decoder)?;
+            let len = u64::decode(decoder)?;
+            let bit_len = u64::decode(decoder)?;
+            let bits = Vec::<u8>::decode(decoder)?;
+            let checkpoints = Vec::<(u64, u64)>::decode(decoder)?;
+            Self::from_parts(
+                interval
+                    .try_into()
+                    .map_err(|_| DecodeError::OtherString("interval overflow".to_owned()))?,
+                len.try_into()
+                    .map_err(|_| DecodeError::OtherString("length overflow".to_owned()))?,
+                bits,
+                bit_len,
+                checkpoints,
+            )
+            .map_err(|e| DecodeError::OtherString(e.to_string()))
+        }
+    }
+
+    impl<'de, C, T> bincode::BorrowDecode<'de, C> for DeltaOfDelta<T> {
+        fn borrow_decode<D: bincode::de::BorrowDecoder<'de, Context = C>>(
+            decoder: &mut D,
+        ) -> Result<Self, DecodeError> {
+            Self::decode(decoder)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeltaOfDelta;
+    use crate::{Monotonic, Timestamp};
+
+    fn sample() -> Vec<Timestamp> {
+        let mut values = Vec::new();
+        let mut t = 1_713_027_747_000_000_000_u64;
+        for i in 0..1_000_u64 {
+            // 100ms sampling with jitter, occasional backward steps and gaps
+            t = match i % 97 {
+                13 => t - 5_000,
+                50 => t + 3_600_000_000_000,
+                _ => t + 100_000_000 + (i * 7_919) % 1_000,
+            };
+            values.push(Timestamp::from_nanos(t));
+        }
+        values
+    }
+
+    #[test]
+    fn test_compress_roundtrip() {
+        let values = sample();
+        let mut seq = DeltaOfDelta::with_checkpoint_interval(64);
+        for v in &values {
+            seq.push(*v).unwrap();
+        }
+        assert_eq!(seq.len(), values.len());
+        assert_eq!(seq.iter().collect::<Vec<_>>(), values);
+        for i in [0, 1, 63, 64, 65, 500, 999] {
+            assert_eq!(seq.get(i), Some(values[i]));
+        }
+        assert_eq!(seq.get(1_000), None);
+        assert_eq!(seq.last(), values.last().copied());
+        assert!(seq.compressed_size() < values.len() * 8 / 2);
+        let mut seq = DeltaOfDelta::new();
+        seq.push(Monotonic::from_nanos(u64::MAX)).unwrap();
+        seq.push(Monotonic::from_nanos(0)).unwrap();
+        seq.push(Monotonic::from_nanos(u64::MAX)).unwrap();
+        assert_eq!(
+            seq.iter().map(u128::from).collect::<Vec<_>>(),
+            [u128::from(u64::MAX), 0, u128::from(u64::MAX)]
+        );
+        assert!(seq.push(Monotonic::from_secs(u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_compress_regular() {
+        let mut seq = DeltaOfDelta::new();
+        for i in 0..10_000 {
+            seq.push(Monotonic::from_millis(i * 10)).unwrap();
+        }
+        assert!(seq.compressed_size() < 3_000);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_compress_serde() {
+        let mut seq = DeltaOfDelta::with_checkpoint_interval(16);
+        for v in sample() {
+            seq.push(v).unwrap();
+        }
+        let s = serde_json::to_string(&seq).unwrap();
+        let restored: DeltaOfDelta<Timestamp> = serde_json::from_str(&s).unwrap();
+        assert_eq!(restored, seq);
+        let mut value: serde_json::Value = serde_json::from_str(&s).unwrap();
+        value["bit_len"] = (seq.bit_len - 1).into();
+        assert!(serde_json::from_value::<DeltaOfDelta<Timestamp>>(value).is_err());
+        // pushing after deserialization
+        let mut seq = DeltaOfDelta::new();
+        for i in 1..=3 {
+            seq.push(Monotonic::from_nanos(i * 10)).unwrap();
+        }
+        assert_ne!(seq.bit_len % 8, 0);
+        let mut value = serde_json::to_value(&seq).unwrap();
+        let last = value["bits"].as_array().unwrap().len() - 1;
+        value["bits"][last] = (seq.bits[last] | 1).into();
+        let err = serde_json::from_value::<DeltaOfDelta<Monotonic>>(value).unwrap_err();
+        assert!(err.to_string().contains("padding"), "{}", err);
+        let mut restored: DeltaOfDelta<Monotonic> =
+            serde_json::from_value(serde_json::to_value(&seq).unwrap()).unwrap();
+        for i in 4..=5 {
+            restored.push(Monotonic::from_nanos(i * 10)).unwrap();
+        }
+        assert_eq!(restored.len(), 5);
+        assert_eq!(
+            restored.iter().collect::<Vec<_>>(),
+            (1..=5)
+                .map(|i| Monotonic::from_nanos(i * 10))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_compress_bincode() {
+        let mut seq = DeltaOfDelta::new();
+        for v in sample() {
+            seq.push(v).unwrap();
+        }
+        let config = bincode::config::standard();
+        let encoded = bincode::encode_to_vec(&seq, config).unwrap();
+        let (mut restored, _): (DeltaOfDelta<Timestamp>, _) =
+            bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(restored, seq);
+        restored.push(Timestamp::from_secs(1)).unwrap();
+        assert_eq!(restored.last(), Some(Timestamp::from_secs(1)));
+        assert_eq!(restored.get(1_000), Some(Timestamp::from_secs(1)));
+    }
+}
@@ -13,7 +13,7 @@ impl FromStr for Timestamp {
         }
         #[cfg(feature = "as-float-secs")]
         if let Ok(v) = s.parse::<f64>() {
-            return Ok(v.into());
+            return timestamp_from_secs_f64(v);
         }
         dateparser::parse(s)
             .map_err(|e| Error::Parse(e.to_string()))?
@@ -30,10 +30,17 @@ impl FromStr for Timestamp {
     }
     #[cfg(feature = "as-float-secs")]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(s.parse::<f64>()?.into())
+        timestamp_from_secs_f64(s.parse::<f64>()?)
     }
 }
 
+#[cfg(feature = "as-float-secs")]
+fn timestamp_from_secs_f64(value: f64) -> Result<Timestamp, Error> {
+    Duration::try_from_secs_f64(value)
+        .map(Into::into)
+        .map_err(|e| Error::Convert(format!("invalid time {} seconds: {}", value, e)))
+}
+
 impl FromStr for Monotonic {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -0,0 +1,305 @@
+use std::{str::FromStr, time::Duration};
+
+use crate::{Error, Timestamp};
+
+/// Units of numeric epoch timestamps
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum EpochUnit {
+    Secs,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl EpochUnit {
+    const ALL: [EpochUnit; 4] = [
+        EpochUnit::Secs,
+        EpochUnit::Millis,
+        EpochUnit::Micros,
+        EpochUnit::Nanos,
+    ];
+    /// Nanoseconds in one unit
+    #[inline]
+    pub fn nanos(self) -> u64 {
+        match self {
+            EpochUnit::Secs => 1_000_000_000,
+            EpochUnit::Millis => 1_000_000,
+            EpochUnit::Micros => 1_000,
+            EpochUnit::Nanos => 1,
+        }
+    }
+    /// Converts a raw epoch value, given in the unit, to a timestamp
+    #[inline]
+    pub fn timestamp(self, value: u64) -> Timestamp {
+        match self {
+            EpochUnit::Secs => Timestamp::from_secs(value),
+            EpochUnit::Millis => Timestamp::from_millis(value),
+            EpochUnit::Micros => Timestamp::from_micros(value),
+            EpochUnit::Nanos => Timestamp::from_nanos(value),
+        }
+    }
+    /// Converts a time value to a number of units, applying the rounding policy
+    pub fn to_units(self, value: Duration, rounding: Rounding) -> Result<u128, Error> {
+        let nanos = value.as_nanos();
+        let unit = u128::from(self.nanos());
+        let rem = nanos % unit;
+        match rounding {
+            Rounding::Floor => Ok(nanos / unit),
+            Rounding::Nearest => Ok(nanos / unit + u128::from(rem * 2 >= unit)),
+            Rounding::Exact if rem == 0 => Ok(nanos / unit),
+            Rounding::Exact => Err(Error::Convert(format!(
+                "{} nanoseconds is not a whole number of {:?}",
+                nanos, self
+            ))),
+        }
+    }
+    /// Converts a raw float epoch value, given in the unit, to a timestamp
+    #[allow(clippy::cast_precision_loss)]
+    pub fn timestamp_f64(self, value: f64) -> Result<Timestamp, Error> {
+        let secs = value / (1_000_000_000 / self.nanos()) as f64;
+        Duration::try_from_secs_f64(secs)
+            .map(Into::into)
+            .map_err(|e| Error::Convert(e.to_string()))
+    }
+}
+
+/// Rounding policy for conversions to coarser time units
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub enum Rounding {
+    /// Truncate extra precision (towards the UNIX epoch)
+    #[default]
+    Floor,
+    /// Round half up to the nearest unit
+    Nearest,
+    /// Fail if the value is not a whole number of units
+    Exact,
+}
+
+/// A plausible date window, used to infer units of numeric epoch timestamps from their magnitude
+///
+/// A value is accepted if exactly one unit puts it inside the window. The default window is
+/// 2000-01-01 - 2100-01-01 (UTC), which is narrow enough to never be ambiguous.
+///
+/// # Example
+///
+/// ```rust
+/// use bma_ts::{EpochUnit, EpochWindow};
+///
+/// let window = EpochWindow::default();
+/// assert_eq!(window.detect(1_713_027_747).unwrap(), EpochUnit::Secs);
+/// assert_eq!(window.detect(1_713_027_747_365).unwrap(), EpochUnit::Millis);
+/// assert_eq!(
+///     window.timestamp(1_713_027_747_365).unwrap().as_nanos(),
+///     1_713_027_747_365_000_000
+/// );
+/// assert!(window.detect(12345).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct EpochWindow {
+    min: Timestamp,
+    max: Timestamp,
+}
+
+impl Default for EpochWindow {
+    fn default() -> Self {
+        Self {
+            // 2000-01-01T00:00:00Z
+            min: Timestamp(Duration::from_secs(946_684_800)),
+            // 2100-01-01T00:00:00Z
+            max: Timestamp(Duration::from_secs(4_102_444_800)),
+        }
+    }
+}
+
+impl EpochWindow {
+    /// Creates a window, returns an error if min is greater than max
+    pub fn new(min: Timestamp, max: Timestamp) -> Result<Self, Error> {
+        if min > max {
+            return Err(Error::Convert(format!(
+                "invalid epoch window: min {} is greater than max {}",
+                min.as_nanos(),
+                max.as_nanos()
+            )));
+        }
+        Ok(Self { min, max })
+    }
+    #[inline]
+    pub fn min(&self) -> Timestamp {
+        self.min
+    }
+    #[inline]
+    pub fn max(&self) -> Timestamp {
+        self.max
+    }
+    /// Infers the unit of a raw epoch value
+    pub fn detect(&self, value: u64) -> Result<EpochUnit, Error> {
+        self.detect_by(value, |unit| u128::from(value) * u128::from(unit.nanos()))
+    }
+    /// Infers the unit of a raw float epoch value
+    #[allow(clippy::cast_precision_loss)]
+    pub fn detect_f64(&self, value: f64) -> Result<EpochUnit, Error> {
+        if !value.is_finite() || value < 0.0 {
+            return Err(Error::Convert(format!("invalid epoch value {}", value)));
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        self.detect_by(value, |unit| (value * unit.nanos() as f64) as u128)
+    }
+    /// Converts a raw epoch value to a timestamp, inferring the unit
+    pub fn timestamp(&self, value: u64) -> Result<Timestamp, Error> {
+        Ok(self.detect(value)?.timestamp(value))
+    }
+    /// Converts a raw float epoch value to a timestamp, inferring the unit
+    pub fn timestamp_f64(&self, value: f64) -> Result<Timestamp, Error> {
+        self.detect_f64(value)?.timestamp_f64(value)
+    }
+    /// Parses a numeric (integer or float) epoch string, inferring the unit
+    pub fn parse(&self, s: &str) -> Result<Timestamp, Error> {
+        let s = s.trim();
+        if let Ok(v) = s.parse::<u64>() {
+            return self.timestamp(v);
+        }
+        match s.parse::<f64>() {
+            Ok(v) => self.timestamp_f64(v),
+            Err(e) => Err(Error::Parse(format!("{}: {}", s, e))),
+        }
+    }
+    fn detect_by<V, F>(&self, value: V, nanos: F) -> Result<EpochUnit, Error>
+    where
+        V: std::fmt::Display,
+        F: Fn(EpochUnit) -> u128,
+    {
+        let (min, max) = (self.min.as_nanos(), self.max.as_nanos());
+        let mut found = None;
+        for unit in EpochUnit::ALL {
+            if (min..=max).contains(&nanos(unit)) {
+                if let Some(prev) = found {
+                    return Err(Error::Convert(format!(
+                        "epoch value {} is ambiguous ({:?} or {:?})",
+                        value, prev, unit
+                    )));
+                }
+                found = Some(unit);
+            }
+        }
+        found.ok_or_else(|| {
+            Error::Convert(format!(
+                "epoch value {} does not fit any unit within the date window",
+                value
+            ))
+        })
+    }
+}
+
+/// A timestamp parsed from a numeric epoch string, the unit is inferred with the default
+/// [`EpochWindow`]
+///
+/// # Example
+///
+/// ```rust
+/// use bma_ts::{EpochAuto, Timestamp};
+///
+/// let EpochAuto(ts) = "1713027747365".parse().unwrap();
+/// assert_eq!(ts, Timestamp::from_millis(1_713_027_747_365));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct EpochAuto(pub Timestamp);
+
+impl FromStr for EpochAuto {
+    type Err = Error;
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Timestamp::parse_epoch_auto(s).map(Self)
+    }
+}
+
+impl From<EpochAuto> for Timestamp {
+    #[inline]
+    fn from(v: EpochAuto) -> Self {
+        v.0
+    }
+}
+
+impl Timestamp {
+    /// Converts a raw epoch value in seconds, milliseconds, microseconds or nanoseconds to a
+    /// timestamp, inferring the unit with the default [`EpochWindow`]
+    #[inline]
+    pub fn from_epoch_auto(value: u64) -> Result<Self, Error> {
+        EpochWindow::default().timestamp(value)
+    }
+    /// Parses a numeric epoch string, inferring the unit with the default [`EpochWindow`]
+    #[inline]
+    pub fn parse_epoch_auto(s: &str) -> Result<Self, Error> {
+        EpochWindow::default().parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{EpochAuto, EpochUnit, EpochWindow, Rounding};
+    use crate::Timestamp;
+
+    #[test]
+    fn test_epoch_detect() {
+        let window = EpochWindow::default();
+        for (value, unit) in [
+            (1_713_027_747, EpochUnit::Secs),
+            (1_713_027_747_365, EpochUnit::Millis),
+            (1_713_027_747_365_123, EpochUnit::Micros),
+            (1_713_027_747_365_123_456, EpochUnit::Nanos),
+        ] {
+            assert_eq!(window.detect(value).unwrap(), unit);
+        }
+        assert_eq!(
+            Timestamp::from_epoch_auto(1_713_027_747_365).unwrap(),
+            Timestamp::from_millis(1_713_027_747_365)
+        );
+        assert!(window.detect(0).is_err());
+        assert!(window.detect(123_456_789_012).is_err());
+        assert!(window.detect(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_epoch_parse() {
+        assert_eq!(
+            Timestamp::parse_epoch_auto("1713027747.5").unwrap(),
+            Timestamp::from_millis(1_713_027_747_500)
+        );
+        assert_eq!(
+            Timestamp::parse_epoch_auto(" 1713027747365123 ").unwrap(),
+            Timestamp::from_micros(1_713_027_747_365_123)
+        );
+        assert!(Timestamp::parse_epoch_auto("-1713027747").is_err());
+        assert!(Timestamp::parse_epoch_auto("yesterday").is_err());
+        let EpochAuto(ts) = "1713027747365123456".parse().unwrap();
+        assert_eq!(ts, Timestamp::from_nanos(1_713_027_747_365_123_456));
+        assert!("12345".parse::<EpochAuto>().is_err());
+    }
+
+    #[test]
+    fn test_to_units() {
+        let d = Duration::from_nanos(1_500_500);
+        let unit = EpochUnit::Millis;
+        assert_eq!(unit.to_units(d, Rounding::Floor).unwrap(), 1);
+        assert_eq!(unit.to_units(d, Rounding::Nearest).unwrap(), 2);
+        assert!(unit.to_units(d, Rounding::Exact).is_err());
+        assert_eq!(
+            EpochUnit::Micros
+                .to_units(Duration::from_micros(1_500), Rounding::Exact)
+                .unwrap(),
+            1_500
+        );
+    }
+
+    #[test]
+    fn test_epoch_ambiguous() {
+        let window = EpochWindow::new(
+            Timestamp::from_secs(0),
+            Timestamp::from_secs(u32::MAX.into()),
+        )
+        .unwrap();
+        assert!(window.detect(1_713_027_747_365).is_err());
+        assert!(EpochWindow::new(Timestamp::from_secs(1), Timestamp::from_secs(0)).is_err());
+    }
+}
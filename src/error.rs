@@ -1,6 +1,10 @@
 use std::num::{ParseFloatError, ParseIntError, TryFromIntError};
 
+/// Errors returned by the crate
+///
+/// The enum is non-exhaustive: new variants may be added without a major version bump
 #[derive(thiserror::Error, Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     #[error("timestamp parse failed {0}")]
     Parse(String),
@@ -8,6 +12,8 @@ pub enum Error {
     ConvertChrono,
     #[error("timestamp number conversion failed: {0}")]
     Convert(String),
+    #[error("timestamp decode failed: {0}")]
+    Decode(String),
     #[error("time went backward")]
     TimeWentBackward,
 }
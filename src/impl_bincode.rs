@@ -1,6 +1,10 @@
 use bincode::{Decode, Encode};
 
-use crate::{Monotonic, Timestamp};
+use crate::{Error, Monotonic, Timestamp, common::nanos_u64};
+
+fn full_range_hint(err: Error) -> bincode::error::EncodeError {
+    bincode::error::EncodeError::OtherString(format!("{}, use bincode_helpers::FullRange", err))
+}
 
 //
 // Timestamp
@@ -11,8 +15,9 @@ impl Encode for Timestamp {
         &self,
         encoder: &mut E,
     ) -> Result<(), bincode::error::EncodeError> {
-        let nanos: u64 = self.as_nanos().try_into().unwrap();
-        nanos.encode(encoder)
+        nanos_u64(self.as_nanos())
+            .map_err(full_range_hint)?
+            .encode(encoder)
     }
 }
 
@@ -55,8 +60,9 @@ impl Encode for Monotonic {
         &self,
         encoder: &mut E,
     ) -> Result<(), bincode::error::EncodeError> {
-        let nanos: u64 = self.as_nanos().try_into().unwrap();
-        nanos.encode(encoder)
+        nanos_u64(self.as_nanos())
+            .map_err(full_range_hint)?
+            .encode(encoder)
     }
 }
 
@@ -93,4 +99,10 @@ mod tests {
             bincode::decode_from_slice(&encoded, bincode::config::standard()).unwrap();
         assert_eq!(mono, decoded);
     }
+
+    #[test]
+    fn test_bincode_out_of_range() {
+        let mono = Monotonic::from_secs(u64::MAX);
+        assert!(bincode::encode_to_vec(mono, bincode::config::standard()).is_err());
+    }
 }
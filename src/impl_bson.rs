@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use crate::{EpochUnit, Error, Rounding, Timestamp};
+
+// DateTime
+
+/// Truncates sub-millisecond precision, use [`Timestamp::try_into_bson_datetime`] to choose a
+/// different rounding policy
+impl TryFrom<Timestamp> for bson::DateTime {
+    type Error = Error;
+    #[inline]
+    fn try_from(t: Timestamp) -> Result<Self, Self::Error> {
+        t.try_into_bson_datetime(Rounding::Floor)
+    }
+}
+
+impl TryFrom<bson::DateTime> for Timestamp {
+    type Error = Error;
+    fn try_from(dt: bson::DateTime) -> Result<Self, Self::Error> {
+        let ms = dt.timestamp_millis();
+        let ms = u64::try_from(ms)
+            .map_err(|_| Error::Convert(format!("BSON date {}ms is before the UNIX epoch", ms)))?;
+        Ok(Self(Duration::from_millis(ms)))
+    }
+}
+
+// Timestamp (the internal MongoDB replication type)
+
+/// The increment is ignored
+impl From<bson::Timestamp> for Timestamp {
+    #[inline]
+    fn from(t: bson::Timestamp) -> Self {
+        Self::from_secs(t.time.into())
+    }
+}
+
+/// Truncates sub-second precision, the increment is set to zero
+impl TryFrom<Timestamp> for bson::Timestamp {
+    type Error = Error;
+    #[inline]
+    fn try_from(t: Timestamp) -> Result<Self, Self::Error> {
+        t.try_into_bson_timestamp(0)
+    }
+}
+
+impl Timestamp {
+    /// Converts the timestamp to BSON date (milliseconds) with the given rounding policy
+    pub fn try_into_bson_datetime(self, rounding: Rounding) -> Result<bson::DateTime, Error> {
+        let ms = EpochUnit::Millis.to_units(self.0, rounding)?;
+        Ok(bson::DateTime::from_millis(ms.try_into()?))
+    }
+    /// Converts the timestamp to BSON timestamp with the given increment, sub-second precision is
+    /// truncated
+    pub fn try_into_bson_timestamp(self, increment: u32) -> Result<bson::Timestamp, Error> {
+        Ok(bson::Timestamp {
+            time: self.0.as_secs().try_into()?,
+            increment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Rounding, Timestamp};
+
+    #[test]
+    fn test_bson_datetime() {
+        let ts = Timestamp::from_micros(1_713_027_747_365_500);
+        let dt = bson::DateTime::try_from(ts).unwrap();
+        assert_eq!(dt.timestamp_millis(), 1_713_027_747_365);
+        assert_eq!(
+            ts.try_into_bson_datetime(Rounding::Nearest)
+                .unwrap()
+                .timestamp_millis(),
+            1_713_027_747_366
+        );
+        assert!(ts.try_into_bson_datetime(Rounding::Exact).is_err());
+        assert_eq!(
+            Timestamp::try_from(dt).unwrap(),
+            Timestamp::from_millis(1_713_027_747_365)
+        );
+        assert!(Timestamp::try_from(bson::DateTime::from_millis(-1)).is_err());
+    }
+
+    #[test]
+    fn test_bson_timestamp() {
+        let ts = Timestamp::from_millis(1_713_027_747_365);
+        let bt = ts.try_into_bson_timestamp(7).unwrap();
+        assert_eq!((bt.time, bt.increment), (1_713_027_747, 7));
+        assert_eq!(Timestamp::from(bt), Timestamp::from_secs(1_713_027_747));
+        assert!(bson::Timestamp::try_from(Timestamp::from_secs(1 << 32)).is_err());
+    }
+}
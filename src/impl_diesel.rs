@@ -0,0 +1,207 @@
+use diesel::{
+    deserialize::{self, FromSql},
+    serialize::{self, Output, ToSql},
+    sql_types::BigInt,
+};
+#[cfg(feature = "diesel-postgres")]
+use diesel::{
+    pg::{Pg, PgValue},
+    sql_types::{self, Timestamptz},
+};
+#[cfg(feature = "diesel-sqlite")]
+use diesel::{
+    serialize::IsNull,
+    sqlite::{Sqlite, SqliteType, SqliteValue},
+};
+
+use crate::{Monotonic, Timestamp, sql_common};
+
+// Timestamp
+
+#[cfg(feature = "diesel-postgres")]
+macro_rules! impl_pg_timestamp {
+    ($st: ty) => {
+        impl ToSql<$st, Pg> for Timestamp {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                ToSql::<BigInt, Pg>::to_sql(&sql_common::pg_micros(*self)?, &mut out.reborrow())
+            }
+        }
+
+        impl FromSql<$st, Pg> for Timestamp {
+            fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+                let us = <i64 as FromSql<BigInt, Pg>>::from_sql(bytes)?;
+                Ok(sql_common::timestamp_from_pg_micros(us)?)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "diesel-postgres")]
+impl_pg_timestamp!(Timestamptz);
+#[cfg(feature = "diesel-postgres")]
+impl_pg_timestamp!(sql_types::Timestamp);
+
+#[cfg(feature = "diesel-sqlite")]
+impl ToSql<BigInt, Sqlite> for Timestamp {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(i64::try_from(*self)?);
+        Ok(IsNull::No)
+    }
+}
+
+/// The same as for sqlx: INTEGER (nanoseconds), TEXT (ISO 8601) and REAL values
+#[cfg(feature = "diesel-sqlite")]
+impl FromSql<BigInt, Sqlite> for Timestamp {
+    fn from_sql(mut value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        Ok(match value.value_type() {
+            Some(SqliteType::Text) => sql_common::timestamp_from_text(value.read_text())?,
+            Some(SqliteType::Double) => sql_common::timestamp_from_real(value.read_double())?,
+            _ => value.read_long().try_into()?,
+        })
+    }
+}
+
+// Monotonic
+
+#[cfg(feature = "diesel-postgres")]
+impl ToSql<BigInt, Pg> for Monotonic {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        ToSql::<BigInt, Pg>::to_sql(&i64::try_from(*self)?, &mut out.reborrow())
+    }
+}
+
+#[cfg(feature = "diesel-postgres")]
+impl FromSql<BigInt, Pg> for Monotonic {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        Ok(<i64 as FromSql<BigInt, Pg>>::from_sql(bytes)?.try_into()?)
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+impl ToSql<BigInt, Sqlite> for Monotonic {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(i64::try_from(*self)?);
+        Ok(IsNull::No)
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+impl FromSql<BigInt, Sqlite> for Monotonic {
+    fn from_sql(mut value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        Ok(value.read_long().try_into()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "diesel-postgres")]
+    use std::num::NonZeroU32;
+
+    #[cfg(feature = "diesel-sqlite")]
+    use diesel::{Connection, RunQueryDsl, SqliteConnection, dsl::sql};
+    use diesel::{IntoSql, sql_types::BigInt};
+    #[cfg(feature = "diesel-postgres")]
+    use diesel::{
+        QueryResult,
+        deserialize::{self, FromSql},
+        pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue},
+        query_builder::{QueryFragment, bind_collector::RawBytesBindCollector},
+        sql_types::{self, Timestamptz},
+    };
+
+    #[cfg(feature = "diesel-postgres")]
+    use crate::sql_common::J2000_EPOCH_US;
+    use crate::{Monotonic, Timestamp};
+
+    #[cfg(feature = "diesel-sqlite")]
+    #[test]
+    fn test_diesel_sqlite() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        let ts = Timestamp::from_nanos(1_713_027_747_365_000_001);
+        let mono = Monotonic::from_secs(5);
+        let (t, m): (Timestamp, Monotonic) =
+            diesel::select((ts.into_sql::<BigInt>(), mono.into_sql::<BigInt>()))
+                .get_result(&mut conn)
+                .unwrap();
+        assert_eq!((t, m), (ts, mono));
+        let raw: i64 = diesel::select(sql::<BigInt>("").bind::<BigInt, _>(ts))
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(raw, 1_713_027_747_365_000_001);
+        let t: Timestamp = diesel::select(sql::<BigInt>("'2024-04-13 17:02:27.5'"))
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(t, Timestamp::from_millis(1_713_027_747_500));
+        let t: Timestamp = diesel::select(sql::<BigInt>("julianday('2024-04-13 17:02:27')"))
+            .get_result(&mut conn)
+            .unwrap();
+        assert!((t.as_secs_f64() - 1_713_027_747.0).abs() < 1e-3);
+        assert!(
+            diesel::select(Timestamp::from_secs(u64::MAX).into_sql::<BigInt>())
+                .get_result::<Timestamp>(&mut conn)
+                .is_err()
+        );
+        assert!(
+            diesel::select(sql::<BigInt>("-1"))
+                .get_result::<Monotonic>(&mut conn)
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "diesel-postgres")]
+    struct NoLookup;
+
+    #[cfg(feature = "diesel-postgres")]
+    impl PgMetadataLookup for NoLookup {
+        fn lookup_type(&mut self, type_name: &str, _schema: Option<&str>) -> PgTypeMetadata {
+            unreachable!("{} is not a built-in type", type_name)
+        }
+    }
+
+    #[cfg(feature = "diesel-postgres")]
+    fn pg_encode<T: QueryFragment<Pg>>(expr: &T) -> QueryResult<Vec<u8>> {
+        let mut collector = RawBytesBindCollector::<Pg>::new();
+        expr.collect_binds(&mut collector, &mut NoLookup, &Pg)?;
+        Ok(collector.binds.remove(0).unwrap())
+    }
+
+    #[cfg(feature = "diesel-postgres")]
+    fn pg_decode<ST, T: FromSql<ST, Pg>>(bytes: &[u8]) -> deserialize::Result<T> {
+        let oid = NonZeroU32::new(1184).unwrap();
+        T::from_sql(PgValue::new(bytes, &oid))
+    }
+
+    #[cfg(feature = "diesel-postgres")]
+    #[test]
+    fn test_diesel_pg() {
+        let ts = Timestamp::from_micros(1_713_027_747_365_001);
+        let us = 1_713_027_747_365_001 - J2000_EPOCH_US;
+        let bytes = pg_encode(&ts.into_sql::<Timestamptz>()).unwrap();
+        assert_eq!(bytes, us.to_be_bytes());
+        assert_eq!(
+            pg_encode(&ts.into_sql::<sql_types::Timestamp>()).unwrap(),
+            bytes
+        );
+        #[cfg(feature = "sqlx")]
+        {
+            let mut buf = sqlx::postgres::PgArgumentBuffer::default();
+            let _ = sqlx::Encode::<sqlx::Postgres>::encode(ts, &mut buf).unwrap();
+            assert_eq!(*buf, bytes);
+        }
+        assert_eq!(pg_decode::<Timestamptz, Timestamp>(&bytes).unwrap(), ts);
+        assert_eq!(
+            pg_decode::<sql_types::Timestamp, Timestamp>(&bytes).unwrap(),
+            ts
+        );
+        assert!(pg_encode(&Timestamp::from_secs(u64::MAX).into_sql::<Timestamptz>()).is_err());
+        for infinity in [i64::MAX, i64::MIN] {
+            assert!(pg_decode::<Timestamptz, Timestamp>(&infinity.to_be_bytes()).is_err());
+            assert!(pg_decode::<sql_types::Timestamp, Timestamp>(&infinity.to_be_bytes()).is_err());
+        }
+        let mono = Monotonic::from_secs(5);
+        let bytes = pg_encode(&mono.into_sql::<BigInt>()).unwrap();
+        assert_eq!(bytes, 5_000_000_000_i64.to_be_bytes());
+        assert_eq!(pg_decode::<BigInt, Monotonic>(&bytes).unwrap(), mono);
+        assert!(pg_decode::<BigInt, Monotonic>(&(-1_i64).to_be_bytes()).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use crate::{Error, Monotonic, Timestamp, common::NANOS_PER_SEC, rfc3339};
+
+/// Max google.protobuf.Duration seconds (about 10,000 years)
+const MAX_DURATION_SECS: u64 = 315_576_000_000;
+
+fn check_nanos(nanos: i32) -> Result<u32, Error> {
+    u32::try_from(nanos)
+        .ok()
+        .filter(|n| *n < NANOS_PER_SEC)
+        .ok_or_else(|| Error::Convert(format!("protobuf nanoseconds {} out of range", nanos)))
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn nanos_i32(d: Duration) -> i32 {
+    // always below 1_000_000_000
+    d.subsec_nanos() as i32
+}
+
+// Timestamp
+
+impl TryFrom<Timestamp> for prost_types::Timestamp {
+    type Error = Error;
+    fn try_from(t: Timestamp) -> Result<Self, Self::Error> {
+        if t.0.as_secs() > rfc3339::MAX_SECS {
+            return Err(Error::Convert(format!(
+                "timestamp {} is after 9999-12-31T23:59:59Z",
+                t
+            )));
+        }
+        Ok(Self {
+            seconds: t.0.as_secs().try_into()?,
+            nanos: nanos_i32(t.0),
+        })
+    }
+}
+
+impl TryFrom<prost_types::Timestamp> for Timestamp {
+    type Error = Error;
+    fn try_from(t: prost_types::Timestamp) -> Result<Self, Self::Error> {
+        let nanos = check_nanos(t.nanos)?;
+        let secs = u64::try_from(t.seconds).map_err(|_| {
+            Error::Convert(format!(
+                "protobuf timestamp {}s is before the UNIX epoch",
+                t.seconds
+            ))
+        })?;
+        if secs > rfc3339::MAX_SECS {
+            return Err(Error::Convert(format!(
+                "protobuf timestamp {}s is after 9999-12-31T23:59:59Z",
+                t.seconds
+            )));
+        }
+        Ok(Self(Duration::new(secs, nanos)))
+    }
+}
+
+impl Timestamp {
+    /// Formats the timestamp as protobuf JSON (RFC 3339, UTC, with 0, 3, 6 or 9 fraction
+    /// digits), e.g. `2024-04-13T17:02:27.365Z`
+    #[inline]
+    pub fn try_to_protobuf_string(self) -> Result<String, Error> {
+        rfc3339::format(self.0)
+    }
+    /// Parses a protobuf JSON timestamp (RFC 3339, offsets other than `Z` are accepted)
+    pub fn try_from_protobuf_str(s: &str) -> Result<Self, Error> {
+        let d = rfc3339::parse(s)?;
+        if d.as_secs() > rfc3339::MAX_SECS {
+            return Err(Error::Convert(format!(
+                "protobuf timestamp {} is after 9999-12-31T23:59:59Z",
+                s
+            )));
+        }
+        Ok(Self(d))
+    }
+}
+
+// Monotonic
+
+impl TryFrom<Monotonic> for prost_types::Duration {
+    type Error = Error;
+    fn try_from(t: Monotonic) -> Result<Self, Self::Error> {
+        if t.0.as_secs() > MAX_DURATION_SECS {
+            return Err(Error::Convert(format!(
+                "duration {} is out of the protobuf range",
+                t
+            )));
+        }
+        Ok(Self {
+            seconds: t.0.as_secs().try_into()?,
+            nanos: nanos_i32(t.0),
+        })
+    }
+}
+
+impl TryFrom<prost_types::Duration> for Monotonic {
+    type Error = Error;
+    fn try_from(d: prost_types::Duration) -> Result<Self, Self::Error> {
+        let nanos = check_nanos(d.nanos)?;
+        let secs = u64::try_from(d.seconds)
+            .map_err(|_| Error::Convert(format!("protobuf duration {}s is negative", d.seconds)))?;
+        if secs > MAX_DURATION_SECS {
+            return Err(Error::Convert(format!(
+                "protobuf duration {}s is out of range",
+                d.seconds
+            )));
+        }
+        Ok(Self(Duration::new(secs, nanos)))
+    }
+}
+
+impl Monotonic {
+    /// Formats the value as a protobuf JSON duration (seconds with 0, 3, 6 or 9 fraction digits
+    /// and the `s` suffix), e.g. `1.500s`
+    pub fn try_to_protobuf_string(self) -> Result<String, Error> {
+        if self.0.as_secs() > MAX_DURATION_SECS {
+            return Err(Error::Convert(format!(
+                "duration {} is out of the protobuf range",
+                self
+            )));
+        }
+        Ok(format!(
+            "{}{}s",
+            self.0.as_secs(),
+            rfc3339::format_fraction(self.0.subsec_nanos())
+        ))
+    }
+    /// Parses a protobuf JSON duration, e.g. `1.500s`
+    pub fn try_from_protobuf_str(s: &str) -> Result<Self, Error> {
+        let d: prost_types::Duration = s
+            .parse()
+            .map_err(|e| Error::Parse(format!("{}: {}", s, e)))?;
+        d.try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Monotonic, Timestamp};
+
+    #[test]
+    fn test_prost_timestamp() {
+        let ts = Timestamp::from_nanos(1_713_027_747_365_000_001);
+        let pt = prost_types::Timestamp::try_from(ts).unwrap();
+        assert_eq!((pt.seconds, pt.nanos), (1_713_027_747, 365_000_001));
+        assert_eq!(Timestamp::try_from(pt).unwrap(), ts);
+        assert!(
+            Timestamp::try_from(prost_types::Timestamp {
+                seconds: -1,
+                nanos: 0
+            })
+            .is_err()
+        );
+        assert!(
+            Timestamp::try_from(prost_types::Timestamp {
+                seconds: 1,
+                nanos: 1_000_000_000
+            })
+            .is_err()
+        );
+        assert!(prost_types::Timestamp::try_from(Timestamp::from_secs(253_402_300_800)).is_err());
+    }
+
+    #[test]
+    fn test_prost_timestamp_json() {
+        let ts = Timestamp::from_millis(1_713_027_747_365);
+        assert_eq!(
+            ts.try_to_protobuf_string().unwrap(),
+            "2024-04-13T17:02:27.365Z"
+        );
+        assert_eq!(
+            Timestamp::try_from_protobuf_str("2024-04-13T19:02:27.365+02:00").unwrap(),
+            ts
+        );
+        assert!(Timestamp::try_from_protobuf_str("2024-04-13").is_err());
+    }
+
+    #[test]
+    fn test_prost_duration() {
+        let mono = Monotonic::from_nanos(1_500_000_000);
+        let pd = prost_types::Duration::try_from(mono).unwrap();
+        assert_eq!((pd.seconds, pd.nanos), (1, 500_000_000));
+        assert_eq!(Monotonic::try_from(pd).unwrap(), mono);
+        assert!(
+            Monotonic::try_from(prost_types::Duration {
+                seconds: -1,
+                nanos: 0
+            })
+            .is_err()
+        );
+        assert_eq!(mono.try_to_protobuf_string().unwrap(), "1.500s");
+        assert_eq!(Monotonic::try_from_protobuf_str("1.5s").unwrap(), mono);
+        assert!(Monotonic::try_from_protobuf_str("-1.5s").is_err());
+    }
+}
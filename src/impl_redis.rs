@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use redis::{
+    ErrorKind, FromRedisValue, NumericBehavior, RedisError, RedisResult, RedisWrite, ToRedisArgs,
+    Value,
+};
+
+use crate::{Error, Monotonic, Timestamp, common::duration_from_nanos};
+
+fn redis_error(value: &Value, err: &Error) -> RedisError {
+    RedisError::from((
+        ErrorKind::TypeError,
+        "Response was of incompatible type",
+        format!("{} (response was {:?})", err, value),
+    ))
+}
+
+macro_rules! impl_redis {
+    ($t: ty) => {
+        /// Written as integer nanoseconds
+        impl ToRedisArgs for $t {
+            fn write_redis_args<W>(&self, out: &mut W)
+            where
+                W: ?Sized + RedisWrite,
+            {
+                self.as_nanos().write_redis_args(out);
+            }
+            fn describe_numeric_behavior(&self) -> NumericBehavior {
+                NumericBehavior::NumberIsInteger
+            }
+        }
+
+        /// Read from integer nanoseconds
+        impl FromRedisValue for $t {
+            fn from_redis_value(v: &Value) -> RedisResult<Self> {
+                let nanos = i128::from_redis_value(v)?;
+                u128::try_from(nanos)
+                    .map_err(Error::from)
+                    .and_then(duration_from_nanos)
+                    .map(Self)
+                    .map_err(|e| redis_error(v, &e))
+            }
+        }
+
+        impl $t {
+            /// Converts the value to a sorted-set score (see [float seconds](crate#float-seconds))
+            pub fn as_redis_score(self) -> f64 {
+                self.as_secs_f64()
+            }
+            /// Converts a sorted-set score (seconds) to the value
+            pub fn from_redis_score(score: f64) -> Result<Self, Error> {
+                Duration::try_from_secs_f64(score)
+                    .map(Self)
+                    .map_err(|e| Error::Convert(format!("invalid score {}: {}", score, e)))
+            }
+        }
+    };
+}
+
+impl_redis!(Timestamp);
+impl_redis!(Monotonic);
+
+impl Timestamp {
+    /// Formats a stream ID (`<millis>-<seq>`), sub-millisecond precision is truncated
+    pub fn to_redis_stream_id(self, seq: u64) -> String {
+        format!("{}-{}", self.as_millis(), seq)
+    }
+    /// Parses a stream ID, returns the timestamp and the sequence number (0 if omitted)
+    pub fn from_redis_stream_id(id: &str) -> Result<(Self, u64), Error> {
+        let (millis, seq) = id.split_once('-').unwrap_or((id, "0"));
+        let parse = |s: &str| {
+            s.parse::<u64>()
+                .map_err(|e| Error::Parse(format!("invalid stream ID {}: {}", id, e)))
+        };
+        Ok((Self::from_millis(parse(millis)?), parse(seq)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use redis::{FromRedisValue, ToRedisArgs, Value};
+
+    use crate::{Monotonic, Timestamp};
+
+    #[test]
+    fn test_redis_value() {
+        let ts = Timestamp::from_nanos(1_713_027_747_365_000_001);
+        assert_eq!(ts.to_redis_args(), [b"1713027747365000001".to_vec()]);
+        let mono = Monotonic::from_secs(u64::MAX);
+        let args = mono.to_redis_args();
+        assert_eq!(
+            Monotonic::from_redis_value(&Value::BulkString(args[0].clone())).unwrap(),
+            mono
+        );
+        assert_eq!(
+            Timestamp::from_redis_value(&Value::Int(1_713_027_747_365_000_001)).unwrap(),
+            ts
+        );
+        assert!(Timestamp::from_redis_value(&Value::Int(-1)).is_err());
+        assert!(Timestamp::from_redis_value(&Value::Nil).is_err());
+    }
+
+    #[test]
+    fn test_redis_score() {
+        let ts = Timestamp::from_millis(1_713_027_747_365);
+        let score = ts.as_redis_score();
+        assert!((score - 1_713_027_747.365).abs() < 1e-6);
+        let decoded = Timestamp::from_redis_score(score).unwrap();
+        assert!(decoded.abs_diff(ts).as_nanos() < 240);
+        let mono = Monotonic::from_nanos(123_456_789_012);
+        assert_eq!(
+            Monotonic::from_redis_score(mono.as_redis_score()).unwrap(),
+            mono
+        );
+        assert!(Timestamp::from_redis_score(-1.0).is_err());
+        assert!(Timestamp::from_redis_score(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_redis_stream_id() {
+        let ts = Timestamp::from_nanos(1_713_027_747_365_000_001);
+        let id = ts.to_redis_stream_id(3);
+        assert_eq!(id, "1713027747365-3");
+        assert_eq!(
+            Timestamp::from_redis_stream_id(&id).unwrap(),
+            (Timestamp::from_millis(1_713_027_747_365), 3)
+        );
+        assert_eq!(
+            Timestamp::from_redis_stream_id("1713027747365").unwrap(),
+            (Timestamp::from_millis(1_713_027_747_365), 0)
+        );
+        assert!(Timestamp::from_redis_stream_id("1713027747365-").is_err());
+        assert!(Timestamp::from_redis_stream_id("*").is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use std::cmp::Ordering;
+use std::time::Duration;
+
+use crate::{ArchivedMonotonic, ArchivedTimestamp, Monotonic, Timestamp};
+
+macro_rules! impl_archived {
+    ($archived: ty, $t: ty) => {
+        impl $archived {
+            #[inline]
+            pub fn as_secs(&self) -> u64 {
+                self.0.as_secs()
+            }
+            #[inline]
+            pub fn as_secs_f64(&self) -> f64 {
+                self.0.as_secs_f64()
+            }
+            #[inline]
+            pub fn as_millis(&self) -> u128 {
+                self.0.as_millis()
+            }
+            #[inline]
+            pub fn as_micros(&self) -> u128 {
+                self.0.as_micros()
+            }
+            #[inline]
+            pub fn as_nanos(&self) -> u128 {
+                self.0.as_nanos()
+            }
+            #[inline]
+            pub fn as_duration(&self) -> Duration {
+                self.0.into()
+            }
+        }
+
+        impl From<&$archived> for $t {
+            #[inline]
+            fn from(value: &$archived) -> Self {
+                Self(value.as_duration())
+            }
+        }
+
+        impl PartialEq<$t> for $archived {
+            #[inline]
+            fn eq(&self, other: &$t) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl PartialOrd<$t> for $archived {
+            #[inline]
+            fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                Some(self.as_duration().cmp(&other.0))
+            }
+        }
+    };
+}
+
+impl_archived!(ArchivedTimestamp, Timestamp);
+impl_archived!(ArchivedMonotonic, Monotonic);
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+
+    use crate::{ArchivedMonotonic, ArchivedTimestamp, Monotonic, Timestamp};
+
+    #[test]
+    fn test_rkyv_timestamp() {
+        let values = vec![
+            Timestamp::from_nanos(1_713_027_747_365_000_001),
+            Timestamp::from_secs(u64::MAX),
+        ];
+        let bytes = rkyv::to_bytes::<Error>(&values).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Vec<Timestamp>>, Error>(&bytes).unwrap();
+        assert_eq!(archived[0].as_secs(), 1_713_027_747);
+        assert_eq!(archived[0].as_nanos(), 1_713_027_747_365_000_001);
+        assert_eq!(archived[1].as_secs(), u64::MAX);
+        assert!(archived[0] < archived[1]);
+        assert!(archived[0] < values[1]);
+        assert_eq!(archived[0], values[0]);
+        assert_eq!(Timestamp::from(&archived[1]), values[1]);
+        let deserialized: Vec<Timestamp> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(deserialized, values);
+    }
+
+    #[test]
+    fn test_rkyv_monotonic() {
+        let mono = Monotonic::from_nanos(2_500_000_000);
+        let bytes = rkyv::to_bytes::<Error>(&mono).unwrap();
+        let archived = rkyv::access::<ArchivedMonotonic, Error>(&bytes).unwrap();
+        assert_eq!(archived.as_millis(), 2_500);
+        assert!(*archived > Monotonic::from_secs(2));
+        assert_eq!(
+            rkyv::deserialize::<Monotonic, Error>(archived).unwrap(),
+            mono
+        );
+        assert!(rkyv::access::<ArchivedTimestamp, Error>(&[0xff; 16]).is_err());
+    }
+}
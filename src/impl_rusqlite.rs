@@ -0,0 +1,196 @@
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, Value, ValueRef};
+
+use crate::{
+    EpochUnit, Error, Monotonic, Timestamp,
+    sql::{IsoText, JulianDay, Nanos, UnixMillis, UnixSecs},
+    sql_common,
+};
+
+fn to_sql_err(err: Error) -> rusqlite::Error {
+    rusqlite::Error::ToSqlConversionFailure(Box::new(err))
+}
+
+fn from_sql_err(err: Error) -> FromSqlError {
+    FromSqlError::Other(Box::new(err))
+}
+
+/// The same representation as for sqlx: INTEGER (nanoseconds, or in the given unit), TEXT
+/// (ISO 8601) and REAL values
+fn timestamp_from_sql(
+    value: ValueRef<'_>,
+    integer_unit: EpochUnit,
+    from_real: fn(f64) -> Result<Timestamp, Error>,
+) -> FromSqlResult<Timestamp> {
+    match value {
+        ValueRef::Integer(v) => {
+            let v = u64::try_from(v).map_err(|_| FromSqlError::OutOfRange(v))?;
+            Ok(integer_unit.timestamp(v))
+        }
+        ValueRef::Text(_) => sql_common::timestamp_from_text(value.as_str()?).map_err(from_sql_err),
+        ValueRef::Real(v) => from_real(v).map_err(from_sql_err),
+        ValueRef::Null | ValueRef::Blob(_) => Err(FromSqlError::InvalidType),
+    }
+}
+
+fn nanos_to_sql<T: TryInto<i64, Error = Error>>(
+    value: T,
+) -> rusqlite::Result<ToSqlOutput<'static>> {
+    Ok(ToSqlOutput::Owned(Value::Integer(
+        value.try_into().map_err(to_sql_err)?,
+    )))
+}
+
+// Timestamp
+
+impl ToSql for Timestamp {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        nanos_to_sql(*self)
+    }
+}
+
+impl FromSql for Timestamp {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        timestamp_from_sql(value, EpochUnit::Nanos, sql_common::timestamp_from_real)
+    }
+}
+
+// Monotonic
+
+impl ToSql for Monotonic {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        nanos_to_sql(*self)
+    }
+}
+
+impl FromSql for Monotonic {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        v.try_into().map_err(|_| FromSqlError::OutOfRange(v))
+    }
+}
+
+// Nanos
+
+impl<T: Copy + TryInto<i64, Error = Error>> ToSql for Nanos<T> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        nanos_to_sql(self.0)
+    }
+}
+
+impl<T: TryFrom<i64, Error = Error>> FromSql for Nanos<T> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        v.try_into()
+            .map(Self)
+            .map_err(|_| FromSqlError::OutOfRange(v))
+    }
+}
+
+// Storage modes
+
+macro_rules! impl_mode {
+    ($t: ty, $integer_unit: expr, $from_real: expr, $to_value: expr) => {
+        impl ToSql for $t {
+            fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+                let f: fn(Timestamp) -> Result<Value, Error> = $to_value;
+                f(self.0).map(ToSqlOutput::Owned).map_err(to_sql_err)
+            }
+        }
+
+        impl FromSql for $t {
+            fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+                timestamp_from_sql(value, $integer_unit, $from_real).map(Self)
+            }
+        }
+    };
+}
+
+impl_mode!(
+    IsoText,
+    EpochUnit::Nanos,
+    sql_common::timestamp_from_real,
+    |t| Ok(Value::Text(sql_common::sqlite_text(t)?))
+);
+impl_mode!(
+    JulianDay,
+    EpochUnit::Nanos,
+    sql_common::timestamp_from_julian_day,
+    |t| Ok(Value::Real(sql_common::julian_day(t)))
+);
+impl_mode!(
+    UnixSecs,
+    EpochUnit::Secs,
+    sql_common::timestamp_from_secs_f64,
+    |t| Ok(Value::Integer(t.as_secs().try_into()?))
+);
+impl_mode!(
+    UnixMillis,
+    EpochUnit::Millis,
+    |v| sql_common::timestamp_from_secs_f64(v / 1_000.0),
+    |t| Ok(Value::Integer(t.as_millis().try_into()?))
+);
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+
+    use crate::{
+        Monotonic, Timestamp,
+        sql::{IsoText, JulianDay, Nanos, UnixMillis, UnixSecs},
+    };
+
+    #[test]
+    fn test_rusqlite() {
+        let conn = Connection::open_in_memory().unwrap();
+        let ts = Timestamp::from_nanos(1_713_027_747_365_000_001);
+        let mono = Monotonic::from_secs(5);
+        let (t, m, n): (Timestamp, Monotonic, Nanos<Timestamp>) = conn
+            .query_row("SELECT ?1, ?2, ?3", (ts, mono, Nanos(ts)), |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap();
+        assert_eq!((t, m, n.0), (ts, mono, ts));
+        let raw: i64 = conn.query_row("SELECT ?1", [ts], |row| row.get(0)).unwrap();
+        assert_eq!(raw, 1_713_027_747_365_000_001);
+        assert!(
+            conn.query_row("SELECT ?1", [Timestamp::from_secs(u64::MAX)], |row| {
+                row.get::<_, Timestamp>(0)
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rusqlite_modes() {
+        let conn = Connection::open_in_memory().unwrap();
+        let ts = Timestamp::from_nanos(1_713_027_747_365_000_001);
+        let s: String = conn
+            .query_row("SELECT datetime(?1)", [IsoText(ts)], |row| row.get(0))
+            .unwrap();
+        assert_eq!(s, "2024-04-13 17:02:27");
+        let t: IsoText = conn
+            .query_row("SELECT ?1", [IsoText(ts)], |row| row.get(0))
+            .unwrap();
+        assert_eq!(t.0, ts);
+        let t: Timestamp = conn
+            .query_row("SELECT julianday(?1)", [IsoText(ts)], |row| row.get(0))
+            .unwrap();
+        assert!((t.as_secs_f64() - ts.as_secs_f64()).abs() < 1e-3);
+        let t: JulianDay = conn
+            .query_row("SELECT ?1", [JulianDay(ts)], |row| row.get(0))
+            .unwrap();
+        assert!((t.0.as_secs_f64() - ts.as_secs_f64()).abs() < 1e-4);
+        let t: UnixSecs = conn
+            .query_row("SELECT unixepoch(?1)", [IsoText(ts)], |row| row.get(0))
+            .unwrap();
+        assert_eq!(t.0, Timestamp::from_secs(1_713_027_747));
+        let t: UnixMillis = conn
+            .query_row("SELECT ?1", [UnixMillis(ts)], |row| row.get(0))
+            .unwrap();
+        assert_eq!(t.0, Timestamp::from_millis(1_713_027_747_365));
+        let t: Timestamp = conn
+            .query_row("SELECT '2024-04-13T17:02:27Z'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(t, Timestamp::from_secs(1_713_027_747));
+    }
+}
@@ -1,9 +1,17 @@
 use core::fmt;
 use std::time::Duration;
 
+use serde::de::{Expected, IgnoredAny, MapAccess, SeqAccess, Unexpected};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{Monotonic, Timestamp};
+use crate::{
+    Error, Monotonic, Timestamp,
+    common::{NANOS_PER_SEC, duration_from_nanos, nanos_u64},
+};
+
+fn full_range_hint<E: serde::ser::Error>(err: Error) -> E {
+    E::custom(format_args!("{}, use serde_helpers::full_range", err))
+}
 
 #[cfg(not(feature = "as-float-secs"))]
 impl Serialize for Timestamp {
@@ -11,7 +19,7 @@ impl Serialize for Timestamp {
     where
         S: Serializer,
     {
-        serializer.serialize_u64(self.as_nanos().try_into().unwrap())
+        serializer.serialize_u64(nanos_u64(self.as_nanos()).map_err(full_range_hint)?)
     }
 }
 
@@ -30,8 +38,124 @@ impl Serialize for Monotonic {
     where
         S: Serializer,
     {
-        serializer.serialize_u64(self.as_nanos().try_into().unwrap())
+        serializer.serialize_u64(nanos_u64(self.as_nanos()).map_err(full_range_hint)?)
+    }
+}
+
+#[cfg(all(feature = "chrono", not(feature = "as-float-secs")))]
+const TIMESTAMP_STR_FORMATS: &str =
+    "integer nanoseconds or a date/time string (RFC 3339 and other common formats)";
+#[cfg(all(feature = "chrono", feature = "as-float-secs"))]
+const TIMESTAMP_STR_FORMATS: &str =
+    "float seconds or a date/time string (RFC 3339 and other common formats)";
+#[cfg(all(not(feature = "chrono"), not(feature = "as-float-secs")))]
+const TIMESTAMP_STR_FORMATS: &str =
+    "integer nanoseconds (date/time strings require the chrono feature)";
+#[cfg(all(not(feature = "chrono"), feature = "as-float-secs"))]
+const TIMESTAMP_STR_FORMATS: &str = "float seconds (date/time strings require the chrono feature)";
+const MONOTONIC_STR_FORMATS: &str = "integer nanoseconds";
+
+const EXPECTING: &str =
+    "a string, float, an unsigned integer, a 2-element array or a map with secs and nanos";
+
+fn invalid_str<E: serde::de::Error>(value: &str, err: &Error, formats: &str) -> E {
+    E::custom(format_args!(
+        "invalid time string {:?}: {}, expected {}",
+        value, err, formats
+    ))
+}
+
+fn non_negative<T, U, E>(value: T) -> Result<U, E>
+where
+    T: Copy + fmt::Display,
+    U: TryFrom<T>,
+    E: serde::de::Error,
+{
+    U::try_from(value).map_err(|_| {
+        E::custom(format_args!(
+            "negative time {} is not supported, expected a value since the UNIX epoch",
+            value
+        ))
+    })
+}
+
+fn duration_from_secs_f64<E: serde::de::Error>(value: f64) -> Result<Duration, E> {
+    if value < 0.0 {
+        return Err(E::custom(format_args!(
+            "negative time {} is not supported, expected a value since the UNIX epoch",
+            value
+        )));
+    }
+    Duration::try_from_secs_f64(value)
+        .map_err(|e| E::custom(format_args!("invalid time {} seconds: {}", value, e)))
+}
+
+/// Strictly parses the `[secs, nanos]` form
+fn duration_from_seq<'de, V>(mut seq: V, expected: &dyn Expected) -> Result<Duration, V::Error>
+where
+    V: SeqAccess<'de>,
+{
+    let s: u64 = seq
+        .next_element()?
+        .ok_or_else(|| serde::de::Error::invalid_length(0, expected))?;
+    let ns: u32 = seq
+        .next_element()?
+        .ok_or_else(|| serde::de::Error::invalid_length(1, expected))?;
+    if ns >= NANOS_PER_SEC {
+        return Err(serde::de::Error::invalid_value(
+            Unexpected::Unsigned(ns.into()),
+            &"nanoseconds below 1_000_000_000",
+        ));
+    }
+    if seq.next_element::<IgnoredAny>()?.is_some() {
+        return Err(serde::de::Error::invalid_length(3, expected));
+    }
+    Ok(Duration::new(s, ns))
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum MapField {
+    #[serde(alias = "secs_since_epoch", alias = "seconds")]
+    Secs,
+    #[serde(alias = "nanos_since_epoch")]
+    Nanos,
+}
+
+/// Parses the map forms: `{ "secs", "nanos" }` (`Duration`), `{ "secs_since_epoch",
+/// "nanos_since_epoch" }` (`SystemTime`) and `{ "seconds", "nanos" }` (protobuf), the nanoseconds
+/// field may be omitted
+fn duration_from_map<'de, V>(mut map: V) -> Result<Duration, V::Error>
+where
+    V: MapAccess<'de>,
+{
+    let mut secs: Option<u64> = None;
+    let mut nanos: Option<u32> = None;
+    while let Some(field) = map.next_key()? {
+        match field {
+            MapField::Secs => {
+                if secs.is_some() {
+                    return Err(serde::de::Error::duplicate_field("secs"));
+                }
+                secs = Some(map.next_value()?);
+            }
+            MapField::Nanos => {
+                if nanos.is_some() {
+                    return Err(serde::de::Error::duplicate_field("nanos"));
+                }
+                nanos = Some(map.next_value()?);
+            }
+        }
+    }
+    let secs = secs.ok_or_else(|| serde::de::Error::missing_field("secs"))?;
+    let nanos = nanos.unwrap_or_default();
+    if nanos >= NANOS_PER_SEC {
+        return Err(serde::de::Error::invalid_value(
+            Unexpected::Unsigned(nanos.into()),
+            &"nanoseconds below 1_000_000_000",
+        ));
     }
+    Ok(Duration::new(secs, nanos))
 }
 
 struct TimestampVisitor;
@@ -40,7 +164,7 @@ impl<'de> serde::de::Visitor<'de> for TimestampVisitor {
     type Value = Timestamp;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("a string, float, an unsigned integer, or a 2-element array")
+        formatter.write_str(EXPECTING)
     }
 
     #[cfg(not(feature = "as-float-secs"))]
@@ -59,39 +183,67 @@ impl<'de> serde::de::Visitor<'de> for TimestampVisitor {
         Ok(Timestamp::from_secs(value))
     }
 
+    #[cfg(not(feature = "as-float-secs"))]
+    fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(duration_from_nanos(value)
+            .map_err(serde::de::Error::custom)?
+            .into())
+    }
+
+    #[cfg(feature = "as-float-secs")]
+    fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let secs = u64::try_from(value).map_err(|_| {
+            serde::de::Error::custom(format_args!("timestamp {} seconds is out of range", value))
+        })?;
+        self.visit_u64(secs)
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_u64(non_negative(value)?)
+    }
+
+    fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_u128(non_negative(value)?)
+    }
+
     fn visit_f32<E>(self, value: f32) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        Ok(value.into())
+        self.visit_f64(value.into())
     }
 
     fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        Ok(value.into())
+        duration_from_secs_f64(value).map(Into::into)
     }
 
-    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    fn visit_seq<V>(self, seq: V) -> Result<Self::Value, V::Error>
     where
         V: serde::de::SeqAccess<'de>,
     {
-        let s: u64 = seq
-            .next_element()?
-            .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
-        let ns: u32 = seq
-            .next_element()?
-            .ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
-        Ok(Duration::new(s, ns).into())
+        Ok(duration_from_seq(seq, &self)?.into())
     }
-    fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+
+    fn visit_map<V>(self, map: V) -> Result<Self::Value, V::Error>
     where
-        E: serde::de::Error,
+        V: serde::de::MapAccess<'de>,
     {
-        value
-            .parse()
-            .map_err(|_| serde::de::Error::custom("invalid time string"))
+        Ok(duration_from_map(map)?.into())
     }
     fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
     where
@@ -99,7 +251,16 @@ impl<'de> serde::de::Visitor<'de> for TimestampVisitor {
     {
         value
             .parse()
-            .map_err(|_| serde::de::Error::custom("invalid time string"))
+            .map_err(|e| invalid_str(value, &e, TIMESTAMP_STR_FORMATS))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let s = std::str::from_utf8(value)
+            .map_err(|_| serde::de::Error::invalid_value(Unexpected::Bytes(value), &self))?;
+        self.visit_str(s)
     }
 }
 
@@ -118,7 +279,7 @@ impl<'de> serde::de::Visitor<'de> for MonotonicVisitor {
     type Value = Monotonic;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("a string, float, an unsigned integer, or a 2-element array")
+        formatter.write_str(EXPECTING)
     }
 
     fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
@@ -128,48 +289,73 @@ impl<'de> serde::de::Visitor<'de> for MonotonicVisitor {
         Ok(value.into())
     }
 
+    fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(duration_from_nanos(value)
+            .map_err(serde::de::Error::custom)?
+            .into())
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_u64(non_negative(value)?)
+    }
+
+    fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_u128(non_negative(value)?)
+    }
+
     fn visit_f32<E>(self, value: f32) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        Ok(value.into())
+        self.visit_f64(value.into())
     }
 
     fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        Ok(value.into())
+        duration_from_secs_f64(value).map(Into::into)
     }
 
-    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    fn visit_seq<V>(self, seq: V) -> Result<Self::Value, V::Error>
     where
         V: serde::de::SeqAccess<'de>,
     {
-        let s: u64 = seq
-            .next_element()?
-            .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
-        let ns: u32 = seq
-            .next_element()?
-            .ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
-        Ok(Duration::new(s, ns).into())
+        Ok(duration_from_seq(seq, &self)?.into())
+    }
+
+    fn visit_map<V>(self, map: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::MapAccess<'de>,
+    {
+        Ok(duration_from_map(map)?.into())
     }
-    fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
         value
             .parse()
-            .map_err(|_| serde::de::Error::custom("invalid time string"))
+            .map_err(|e| invalid_str(value, &e, MONOTONIC_STR_FORMATS))
     }
 
-    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        value
-            .parse()
-            .map_err(|_| serde::de::Error::custom("invalid time string"))
+        let s = std::str::from_utf8(value)
+            .map_err(|_| serde::de::Error::invalid_value(Unexpected::Bytes(value), &self))?;
+        self.visit_str(s)
     }
 }
 
@@ -181,3 +367,85 @@ impl<'de> Deserialize<'de> for Monotonic {
         deserializer.deserialize_any(MonotonicVisitor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::{Monotonic, Timestamp};
+
+    fn err<T: serde::de::DeserializeOwned + std::fmt::Debug>(s: &str) -> String {
+        serde_json::from_str::<T>(s).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_deserialize_seq() {
+        let ts: Timestamp = serde_json::from_str("[1713027747, 365000000]").unwrap();
+        assert_eq!(ts, Timestamp::from_millis(1_713_027_747_365));
+        assert!(err::<Timestamp>("[1, 1000000000]").contains("nanoseconds below"));
+        assert!(err::<Timestamp>("[1, 2, 3]").contains("invalid length 3"));
+        assert!(err::<Monotonic>("[1]").contains("invalid length 1"));
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        let e = err::<Monotonic>(r#""abc""#);
+        assert!(e.contains(r#""abc""#), "{}", e);
+        assert!(e.contains("invalid digit"), "{}", e);
+        assert!(e.contains("expected integer nanoseconds"), "{}", e);
+        assert!(err::<Timestamp>("-5").contains("negative time -5"));
+        assert!(err::<Monotonic>("-5").contains("negative time -5"));
+        assert!(err::<Timestamp>("-1.5").contains("negative time -1.5"));
+        assert!(err::<Monotonic>("-0.000001").contains("negative time -0.000001"));
+        assert!(err::<Timestamp>("1e300").contains("invalid time 1000"));
+        assert!(err::<Monotonic>("1e300").contains("invalid time 1000"));
+        let nan = serde::de::value::F64Deserializer::<serde::de::value::Error>::new(f64::NAN);
+        assert!(Timestamp::deserialize(nan).is_err());
+        let nan = serde::de::value::F32Deserializer::<serde::de::value::Error>::new(f32::NAN);
+        assert!(Monotonic::deserialize(nan).is_err());
+        assert_eq!(
+            serde_json::from_str::<Monotonic>("1.5").unwrap(),
+            Monotonic::from_millis(1_500)
+        );
+    }
+
+    #[cfg(feature = "as-float-secs")]
+    #[test]
+    fn test_deserialize_float_str() {
+        assert_eq!(
+            serde_json::from_str::<Timestamp>(r#""1.5""#).unwrap(),
+            Timestamp::from_millis(1_500)
+        );
+        assert!(err::<Timestamp>(r#""-1.5""#).contains("invalid time -1.5 seconds"));
+        assert!("1e300".parse::<Timestamp>().is_err());
+    }
+
+    #[test]
+    fn test_deserialize_map() {
+        let t = std::time::UNIX_EPOCH + std::time::Duration::new(1_713_027_747, 365);
+        let ts: Timestamp = serde_json::from_value(serde_json::to_value(t).unwrap()).unwrap();
+        assert_eq!(ts, Timestamp::from_nanos(1_713_027_747_000_000_365));
+        let d = std::time::Duration::new(12, 34);
+        let mono: Monotonic = serde_json::from_value(serde_json::to_value(d).unwrap()).unwrap();
+        assert_eq!(mono.as_duration(), d);
+        let ts: Timestamp = serde_json::from_str(r#"{"seconds":1713027747}"#).unwrap();
+        assert_eq!(ts, Timestamp::from_secs(1_713_027_747));
+        assert!(err::<Timestamp>(r#"{"nanos":1}"#).contains("missing field `secs`"));
+        assert!(err::<Timestamp>(r#"{"secs":1,"nanos":1000000000}"#).contains("nanoseconds below"));
+        assert!(err::<Timestamp>(r#"{"secs":1,"millis":1}"#).contains("unknown field"));
+    }
+
+    #[test]
+    fn test_deserialize_wide() {
+        let mono: Monotonic = serde_json::from_str("5").unwrap();
+        assert_eq!(mono, Monotonic::from_nanos(5));
+        let v = serde::de::value::U128Deserializer::<serde::de::value::Error>::new(
+            u128::from(u64::MAX) * 10,
+        );
+        let mono: Monotonic = serde::Deserialize::deserialize(v).unwrap();
+        assert_eq!(mono.as_nanos(), u128::from(u64::MAX) * 10);
+        let v = serde::de::value::BytesDeserializer::<serde::de::value::Error>::new(b"42");
+        let mono: Monotonic = serde::Deserialize::deserialize(v).unwrap();
+        assert_eq!(mono, Monotonic::from_nanos(42));
+    }
+}
@@ -1,9 +1,19 @@
-use crate::{Monotonic, Timestamp};
+use std::time::Duration;
+
+use crate::{
+    EpochUnit, Error, Monotonic, Timestamp,
+    sql::{Bounded, Interval, IsoText, JulianDay, Nanos, TimestampRange, UnixMillis, UnixSecs},
+    sql_common,
+};
 use sqlx::{
-    Decode, Encode, Postgres, Sqlite, Type,
+    Any, Database, Decode, Encode, Postgres, Sqlite, Type, TypeInfo, ValueRef,
+    any::{AnyTypeInfo, AnyValueRef},
     encode::IsNull,
     error::BoxDynError,
-    postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef},
+    postgres::{
+        PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef,
+        types::{PgInterval, PgRange},
+    },
     sqlite::{SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef},
 };
 
@@ -16,28 +26,101 @@ impl Type<Sqlite> for Timestamp {
         <i64 as Type<Sqlite>>::type_info()
     }
     fn compatible(ty: &SqliteTypeInfo) -> bool {
-        *ty == <i64 as Type<Sqlite>>::type_info()
-            || *ty == <i32 as Type<Sqlite>>::type_info()
-            || *ty == <i16 as Type<Sqlite>>::type_info()
-            || *ty == <i8 as Type<Sqlite>>::type_info()
+        <Monotonic as Type<Sqlite>>::compatible(ty)
+            || <str as Type<Sqlite>>::compatible(ty)
+            || <f64 as Type<Sqlite>>::compatible(ty)
     }
 }
 impl<'q> Encode<'q, Sqlite> for Timestamp {
     fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> ResultIsNull {
-        args.push(SqliteArgumentValue::Int64(
-            (*self).try_into().expect("timestamp too large"),
-        ));
+        args.push(SqliteArgumentValue::Int64((*self).try_into()?));
         Ok(IsNull::No)
     }
 }
+/// Decodes INTEGER nanoseconds, TEXT (ISO 8601) and REAL (Julian day or UNIX seconds, see
+/// [`sql::JulianDay`](crate::sql::JulianDay))
 impl<'r> Decode<'r, Sqlite> for Timestamp {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        let value = <i64 as Decode<Sqlite>>::decode(value)?;
-        Ok(value.try_into()?)
+        decode_sqlite(value, EpochUnit::Nanos, sql_common::timestamp_from_real)
+    }
+}
+
+fn decode_sqlite(
+    value: SqliteValueRef<'_>,
+    integer_unit: EpochUnit,
+    from_real: fn(f64) -> Result<Timestamp, Error>,
+) -> Result<Timestamp, BoxDynError> {
+    match value.type_info().name() {
+        "TEXT" => {
+            let s = <&str as Decode<Sqlite>>::decode(value)?;
+            Ok(sql_common::timestamp_from_text(s)?)
+        }
+        "REAL" => {
+            let v = <f64 as Decode<Sqlite>>::decode(value)?;
+            Ok(from_real(v)?)
+        }
+        _ => {
+            let v = <i64 as Decode<Sqlite>>::decode(value)?;
+            Ok(integer_unit.timestamp(v.try_into()?))
+        }
     }
 }
 
-const J2000_EPOCH_US: i64 = 946_684_800_000_000;
+macro_rules! impl_sqlite_mode {
+    ($t: ty, $ty: ty, $integer_unit: expr, $from_real: expr, $encode: expr) => {
+        impl Type<Sqlite> for $t {
+            fn type_info() -> SqliteTypeInfo {
+                <$ty as Type<Sqlite>>::type_info()
+            }
+            fn compatible(ty: &SqliteTypeInfo) -> bool {
+                <Timestamp as Type<Sqlite>>::compatible(ty)
+            }
+        }
+        impl<'q> Encode<'q, Sqlite> for $t {
+            fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> ResultIsNull {
+                let f: fn(Timestamp) -> Result<SqliteArgumentValue<'q>, Error> = $encode;
+                args.push(f(self.0)?);
+                Ok(IsNull::No)
+            }
+        }
+        impl<'r> Decode<'r, Sqlite> for $t {
+            fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+                decode_sqlite(value, $integer_unit, $from_real).map(Self)
+            }
+        }
+    };
+}
+
+impl_sqlite_mode!(
+    IsoText,
+    str,
+    EpochUnit::Nanos,
+    sql_common::timestamp_from_real,
+    |t| Ok(SqliteArgumentValue::Text(
+        sql_common::sqlite_text(t)?.into()
+    ))
+);
+impl_sqlite_mode!(
+    JulianDay,
+    f64,
+    EpochUnit::Nanos,
+    sql_common::timestamp_from_julian_day,
+    |t| Ok(SqliteArgumentValue::Double(sql_common::julian_day(t)))
+);
+impl_sqlite_mode!(
+    UnixSecs,
+    i64,
+    EpochUnit::Secs,
+    sql_common::timestamp_from_secs_f64,
+    |t| Ok(SqliteArgumentValue::Int64(t.as_secs().try_into()?))
+);
+impl_sqlite_mode!(
+    UnixMillis,
+    i64,
+    EpochUnit::Millis,
+    |v| sql_common::timestamp_from_secs_f64(v / 1_000.0),
+    |t| Ok(SqliteArgumentValue::Int64(t.as_millis().try_into()?))
+);
 
 impl Type<Postgres> for Timestamp {
     fn type_info() -> PgTypeInfo {
@@ -60,8 +143,7 @@ impl PgHasArrayType for Timestamp {
 
 impl Encode<'_, Postgres> for Timestamp {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> ResultIsNull {
-        let us = i64::try_from(self.as_micros()).expect("timestamp too large") - J2000_EPOCH_US;
-        Encode::<Postgres>::encode(us, buf)
+        Encode::<Postgres>::encode(sql_common::pg_micros(*self)?, buf)
     }
     fn size_hint(&self) -> usize {
         std::mem::size_of::<i64>()
@@ -70,10 +152,57 @@ impl Encode<'_, Postgres> for Timestamp {
 impl<'r> Decode<'r, Postgres> for Timestamp {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
         let us: i64 = Decode::<Postgres>::decode(value)?;
-        Ok(Timestamp::from_micros((us + J2000_EPOCH_US).try_into()?))
+        Ok(sql_common::timestamp_from_pg_micros(us)?)
+    }
+}
+
+// Bounded
+
+impl Type<Postgres> for Bounded {
+    fn type_info() -> PgTypeInfo {
+        <Timestamp as Type<Postgres>>::type_info()
+    }
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <Timestamp as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for Bounded {
+    fn array_type_info() -> PgTypeInfo {
+        Timestamp::array_type_info()
+    }
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        Timestamp::array_compatible(ty)
     }
 }
 
+impl Encode<'_, Postgres> for Bounded {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> ResultIsNull {
+        match self {
+            Bounded::NegInfinity => Encode::<Postgres>::encode(sql_common::PG_NEG_INFINITY, buf),
+            Bounded::Finite(t) => Encode::<Postgres>::encode_by_ref(t, buf),
+            Bounded::Infinity => Encode::<Postgres>::encode(sql_common::PG_INFINITY, buf),
+        }
+    }
+    fn size_hint(&self) -> usize {
+        std::mem::size_of::<i64>()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Bounded {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(bounded_from_pg_micros(Decode::<Postgres>::decode(value)?)?)
+    }
+}
+
+fn bounded_from_pg_micros(us: i64) -> Result<Bounded, Error> {
+    Ok(match us {
+        sql_common::PG_NEG_INFINITY => Bounded::NegInfinity,
+        sql_common::PG_INFINITY => Bounded::Infinity,
+        _ => Bounded::Finite(sql_common::timestamp_from_pg_micros(us)?),
+    })
+}
+
 // Monotonic
 
 impl Type<Sqlite> for Monotonic {
@@ -89,9 +218,7 @@ impl Type<Sqlite> for Monotonic {
 }
 impl<'q> Encode<'q, Sqlite> for Monotonic {
     fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> ResultIsNull {
-        args.push(SqliteArgumentValue::Int64(
-            (*self).try_into().expect("timestamp too large"),
-        ));
+        args.push(SqliteArgumentValue::Int64((*self).try_into()?));
         Ok(IsNull::No)
     }
 }
@@ -116,8 +243,8 @@ impl PgHasArrayType for Monotonic {
 
 impl Encode<'_, Postgres> for Monotonic {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> ResultIsNull {
-        let us = i64::try_from(self.as_nanos()).expect("timestamp too large");
-        Encode::<Postgres>::encode(us, buf)
+        let ns = i64::try_from(self.as_nanos())?;
+        Encode::<Postgres>::encode(ns, buf)
     }
     fn size_hint(&self) -> usize {
         std::mem::size_of::<i64>()
@@ -130,3 +257,381 @@ impl<'r> Decode<'r, Postgres> for Monotonic {
         Ok(Monotonic::from_nanos(ns.try_into()?))
     }
 }
+
+// Any (BIGINT nanoseconds for all backends)
+
+macro_rules! impl_any {
+    ($t: ty) => {
+        impl Type<Any> for $t {
+            fn type_info() -> AnyTypeInfo {
+                <i64 as Type<Any>>::type_info()
+            }
+            fn compatible(ty: &AnyTypeInfo) -> bool {
+                <i64 as Type<Any>>::compatible(ty)
+            }
+        }
+        impl<'q> Encode<'q, Any> for $t {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <Any as Database>::ArgumentBuffer<'q>,
+            ) -> ResultIsNull {
+                Encode::<Any>::encode(i64::try_from(*self)?, buf)
+            }
+        }
+        impl<'r> Decode<'r, Any> for $t {
+            fn decode(value: AnyValueRef<'r>) -> Result<Self, BoxDynError> {
+                let ns = <i64 as Decode<Any>>::decode(value)?;
+                Ok(ns.try_into()?)
+            }
+        }
+    };
+}
+
+impl_any!(Timestamp);
+impl_any!(Monotonic);
+
+// Nanos
+
+macro_rules! impl_nanos {
+    ($t: ty) => {
+        impl Type<Sqlite> for Nanos<$t> {
+            fn type_info() -> SqliteTypeInfo {
+                <$t as Type<Sqlite>>::type_info()
+            }
+            fn compatible(ty: &SqliteTypeInfo) -> bool {
+                <$t as Type<Sqlite>>::compatible(ty)
+            }
+        }
+        impl<'q> Encode<'q, Sqlite> for Nanos<$t> {
+            fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> ResultIsNull {
+                args.push(SqliteArgumentValue::Int64(self.0.try_into()?));
+                Ok(IsNull::No)
+            }
+        }
+        impl<'r> Decode<'r, Sqlite> for Nanos<$t> {
+            fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+                let value = <i64 as Decode<Sqlite>>::decode(value)?;
+                Ok(Self(value.try_into()?))
+            }
+        }
+
+        impl Type<Postgres> for Nanos<$t> {
+            fn type_info() -> PgTypeInfo {
+                PgTypeInfo::with_name("INT8")
+            }
+        }
+        impl PgHasArrayType for Nanos<$t> {
+            fn array_type_info() -> PgTypeInfo {
+                PgTypeInfo::with_name("_INT8")
+            }
+        }
+        impl Encode<'_, Postgres> for Nanos<$t> {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> ResultIsNull {
+                Encode::<Postgres>::encode(i64::try_from(self.0)?, buf)
+            }
+            fn size_hint(&self) -> usize {
+                std::mem::size_of::<i64>()
+            }
+        }
+        impl<'r> Decode<'r, Postgres> for Nanos<$t> {
+            fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+                let ns: i64 = Decode::<Postgres>::decode(value)?;
+                Ok(Self(ns.try_into()?))
+            }
+        }
+    };
+}
+
+impl_nanos!(Timestamp);
+impl_nanos!(Monotonic);
+
+// Interval
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+impl<T: Copy + Into<Duration>> TryFrom<Interval<T>> for PgInterval {
+    type Error = Error;
+    fn try_from(value: Interval<T>) -> Result<Self, Self::Error> {
+        Ok(PgInterval {
+            months: 0,
+            days: 0,
+            microseconds: value.0.into().as_micros().try_into()?,
+        })
+    }
+}
+
+impl<T: From<Duration>> TryFrom<PgInterval> for Interval<T> {
+    type Error = Error;
+    fn try_from(value: PgInterval) -> Result<Self, Self::Error> {
+        if value.months != 0 {
+            return Err(Error::Convert(format!(
+                "interval with {} month(s) can not be converted to a fixed duration",
+                value.months
+            )));
+        }
+        let us = i64::from(value.days)
+            .checked_mul(MICROS_PER_DAY)
+            .and_then(|us| us.checked_add(value.microseconds))
+            .ok_or_else(|| Error::Convert("interval overflow".to_owned()))?;
+        let us =
+            u64::try_from(us).map_err(|_| Error::Convert(format!("negative interval {}us", us)))?;
+        Ok(Self(Duration::from_micros(us).into()))
+    }
+}
+
+impl<T> Type<Postgres> for Interval<T> {
+    fn type_info() -> PgTypeInfo {
+        PgInterval::type_info()
+    }
+}
+
+impl<T> PgHasArrayType for Interval<T> {
+    fn array_type_info() -> PgTypeInfo {
+        PgInterval::array_type_info()
+    }
+}
+
+impl<T: Copy + Into<Duration>> Encode<'_, Postgres> for Interval<T> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> ResultIsNull {
+        Encode::<Postgres>::encode(PgInterval::try_from(*self)?, buf)
+    }
+    fn size_hint(&self) -> usize {
+        2 * std::mem::size_of::<i64>()
+    }
+}
+
+impl<'r, T: From<Duration>> Decode<'r, Postgres> for Interval<T> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let interval: PgInterval = Decode::<Postgres>::decode(value)?;
+        Ok(interval.try_into()?)
+    }
+}
+
+// TimestampRange
+
+impl From<TimestampRange> for PgRange<Timestamp> {
+    #[inline]
+    fn from(r: TimestampRange) -> Self {
+        PgRange {
+            start: r.start,
+            end: r.end,
+        }
+    }
+}
+
+impl From<PgRange<Timestamp>> for TimestampRange {
+    #[inline]
+    fn from(r: PgRange<Timestamp>) -> Self {
+        Self::new(r.start, r.end)
+    }
+}
+
+impl Type<Postgres> for TimestampRange {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("TSTZRANGE")
+    }
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::with_name("TSTZRANGE") || *ty == PgTypeInfo::with_name("TSRANGE")
+    }
+}
+
+impl PgHasArrayType for TimestampRange {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_TSTZRANGE")
+    }
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::with_name("_TSTZRANGE") || *ty == PgTypeInfo::with_name("_TSRANGE")
+    }
+}
+
+impl Encode<'_, Postgres> for TimestampRange {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> ResultIsNull {
+        Encode::<Postgres>::encode(PgRange::from(*self), buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for TimestampRange {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        // sqlx decodes empty ranges as unbounded ones
+        if is_empty_range(value.format(), value.as_bytes()?) {
+            return Err("empty timestamp ranges are not supported".into());
+        }
+        let range: PgRange<Timestamp> = Decode::<Postgres>::decode(value)?;
+        Ok(range.into())
+    }
+}
+
+fn is_empty_range(format: PgValueFormat, value: &[u8]) -> bool {
+    const RANGE_EMPTY: u8 = 0x01;
+    match format {
+        PgValueFormat::Binary => value.first().is_some_and(|flags| flags & RANGE_EMPTY != 0),
+        PgValueFormat::Text => value.eq_ignore_ascii_case(b"empty"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use std::ops::Bound;
+
+    use sqlx::{
+        Arguments, Encode, Postgres, Type,
+        any::AnyArguments,
+        postgres::{
+            PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat,
+            types::{PgInterval, PgRange},
+        },
+    };
+
+    use crate::{
+        Monotonic, Timestamp,
+        sql::{Bounded, Interval, Nanos, TimestampRange},
+        sql_common::{J2000_EPOCH_US, timestamp_from_pg_micros},
+    };
+
+    use super::{bounded_from_pg_micros, is_empty_range};
+
+    #[test]
+    fn test_pg_interval() {
+        let interval =
+            PgInterval::try_from(Interval(Monotonic::from_nanos(300_000_001_999))).unwrap();
+        assert_eq!(
+            (interval.months, interval.days, interval.microseconds),
+            (0, 0, 300_000_001)
+        );
+        let interval = PgInterval {
+            months: 0,
+            days: 2,
+            microseconds: -1,
+        };
+        assert_eq!(
+            Interval::<Duration>::try_from(interval).unwrap().0,
+            Duration::from_micros(172_799_999_999)
+        );
+        let interval = PgInterval {
+            months: 0,
+            days: -1,
+            microseconds: 0,
+        };
+        assert!(Interval::<Monotonic>::try_from(interval).is_err());
+        let interval = PgInterval {
+            months: 1,
+            days: 0,
+            microseconds: 0,
+        };
+        assert!(Interval::<Monotonic>::try_from(interval).is_err());
+    }
+
+    #[test]
+    fn test_pg_range() {
+        let start = Timestamp::from_secs(1_713_027_747);
+        let us = i64::try_from(start.as_micros()).unwrap() - J2000_EPOCH_US;
+        let mut buf = PgArgumentBuffer::default();
+        let _ = Encode::<Postgres>::encode(TimestampRange::from(start..), &mut buf).unwrap();
+        // lower bound inclusive, upper bound infinite
+        let mut expected = vec![0x02 | 0x10];
+        expected.extend(8_i32.to_be_bytes());
+        expected.extend(us.to_be_bytes());
+        assert_eq!(*buf, expected);
+        let mut buf = PgArgumentBuffer::default();
+        let _ = Encode::<Postgres>::encode(TimestampRange::from(..), &mut buf).unwrap();
+        assert_eq!(*buf, [0x08 | 0x10]);
+    }
+
+    #[test]
+    fn test_pg_range_decode() {
+        let ts = Timestamp::from_secs(1_713_027_747);
+        for (name, array) in [("TSTZRANGE", "_TSTZRANGE"), ("TSRANGE", "_TSRANGE")] {
+            assert!(TimestampRange::compatible(&PgTypeInfo::with_name(name)));
+            let array = PgTypeInfo::with_name(array);
+            assert!(TimestampRange::array_compatible(&array));
+            assert!(<Vec<TimestampRange> as Type<Postgres>>::compatible(&array));
+        }
+        assert!(!TimestampRange::compatible(&PgTypeInfo::with_name(
+            "INT8RANGE"
+        )));
+        // sqlx decodes unbounded bounds (binary flags 0x08, 0x10) as Bound::Unbounded
+        let range = PgRange {
+            start: Bound::Unbounded,
+            end: Bound::Excluded(ts),
+        };
+        assert_eq!(TimestampRange::from(range), TimestampRange::from(..ts));
+        let range = PgRange {
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+        };
+        assert_eq!(TimestampRange::from(range), TimestampRange::from(..));
+        // empty ranges
+        assert!(is_empty_range(PgValueFormat::Binary, &[0x01]));
+        assert!(is_empty_range(PgValueFormat::Text, b"empty"));
+        assert!(!is_empty_range(PgValueFormat::Binary, &[0x08 | 0x10]));
+        assert!(!is_empty_range(
+            PgValueFormat::Text,
+            b"[\"2024-04-13 17:02:27+00\",)"
+        ));
+        let mut buf = PgArgumentBuffer::default();
+        let _ = Encode::<Postgres>::encode(TimestampRange::from(ts..), &mut buf).unwrap();
+        assert!(!is_empty_range(PgValueFormat::Binary, &buf));
+    }
+
+    #[test]
+    fn test_pg_infinity() {
+        assert_eq!(
+            timestamp_from_pg_micros(-J2000_EPOCH_US).unwrap(),
+            Timestamp::from_secs(0)
+        );
+        assert!(timestamp_from_pg_micros(-J2000_EPOCH_US - 1).is_err());
+        assert!(timestamp_from_pg_micros(i64::MAX).is_err());
+        assert!(timestamp_from_pg_micros(i64::MIN).is_err());
+        assert!(timestamp_from_pg_micros(i64::MAX - 1).is_err());
+        assert_eq!(bounded_from_pg_micros(i64::MAX).unwrap(), Bounded::Infinity);
+        assert_eq!(
+            bounded_from_pg_micros(i64::MIN).unwrap(),
+            Bounded::NegInfinity
+        );
+        assert_eq!(
+            bounded_from_pg_micros(-J2000_EPOCH_US).unwrap(),
+            Bounded::Finite(Timestamp::from_secs(0))
+        );
+        assert!(bounded_from_pg_micros(i64::MAX - 1).is_err());
+        for (value, expected) in [
+            (Bounded::Infinity, i64::MAX),
+            (Bounded::NegInfinity, i64::MIN),
+        ] {
+            let mut buf = PgArgumentBuffer::default();
+            let _ = Encode::<Postgres>::encode(value, &mut buf).unwrap();
+            assert_eq!(*buf, expected.to_be_bytes());
+        }
+        assert!(Bounded::NegInfinity < Bounded::Finite(Timestamp::from_secs(0)));
+        assert_eq!(
+            Bounded::from(Timestamp::from_secs(1)).finite(),
+            Some(Timestamp::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn test_pg_nanos() {
+        let ts = Timestamp::from_nanos(1_713_027_747_365_000_001);
+        let mut buf = PgArgumentBuffer::default();
+        let _ = Encode::<Postgres>::encode(Nanos(ts), &mut buf).unwrap();
+        assert_eq!(*buf, 1_713_027_747_365_000_001_i64.to_be_bytes());
+        let mut buf = PgArgumentBuffer::default();
+        assert!(
+            Encode::<Postgres>::encode(Nanos(Timestamp::from_secs(u64::MAX)), &mut buf).is_err()
+        );
+    }
+
+    #[test]
+    fn test_any() {
+        let mut args = AnyArguments::default();
+        args.add(Timestamp::from_nanos(1_713_027_747_365_000_001))
+            .unwrap();
+        args.add(Monotonic::from_secs(5)).unwrap();
+        assert_eq!(
+            format!("{:?}", args.values.0),
+            "[BigInt(1713027747365000001), BigInt(5000000000)]"
+        );
+        assert!(args.add(Timestamp::from_secs(u64::MAX)).is_err());
+    }
+}
@@ -0,0 +1,241 @@
+use std::time::Duration;
+
+use sqlx::{
+    Decode, Encode, MySql, Type,
+    encode::IsNull,
+    error::BoxDynError,
+    mysql::{MySqlTypeInfo, MySqlValueRef},
+    types::chrono::{DateTime, NaiveDateTime, Utc},
+};
+
+use crate::{Monotonic, Timestamp, rfc3339, sql::Nanos};
+
+type ResultIsNull = Result<IsNull, BoxDynError>;
+
+fn is_integer(ty: &MySqlTypeInfo) -> bool {
+    <i64 as Type<MySql>>::compatible(ty) || <u64 as Type<MySql>>::compatible(ty)
+}
+
+// Timestamp
+
+/// DATETIME(6) in UTC (TIMESTAMP(6) is accepted as well), sub-microsecond precision is truncated
+impl Type<MySql> for Timestamp {
+    fn type_info() -> MySqlTypeInfo {
+        <NaiveDateTime as Type<MySql>>::type_info()
+    }
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <DateTime<Utc> as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, MySql> for Timestamp {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> ResultIsNull {
+        if self.0.as_secs() > rfc3339::MAX_SECS {
+            return Err(
+                format!("timestamp {} is after 9999-12-31 23:59:59", self.as_secs()).into(),
+            );
+        }
+        let dt =
+            DateTime::from_timestamp(self.0.as_secs().try_into()?, self.0.subsec_micros() * 1_000)
+                .ok_or_else(|| format!("timestamp {} is out of the MySQL range", self.as_secs()))?;
+        Encode::<MySql>::encode(dt, buf)
+    }
+}
+
+impl<'r> Decode<'r, MySql> for Timestamp {
+    fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        timestamp_from_datetime(Decode::<MySql>::decode(value)?)
+    }
+}
+
+fn timestamp_from_datetime(dt: DateTime<Utc>) -> Result<Timestamp, BoxDynError> {
+    Ok(Timestamp(Duration::new(
+        dt.timestamp().try_into()?,
+        dt.timestamp_subsec_nanos(),
+    )))
+}
+
+// Monotonic
+
+impl Type<MySql> for Monotonic {
+    fn type_info() -> MySqlTypeInfo {
+        <i64 as Type<MySql>>::type_info()
+    }
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        is_integer(ty)
+    }
+}
+
+impl Encode<'_, MySql> for Monotonic {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> ResultIsNull {
+        Encode::<MySql>::encode(i64::try_from(*self)?, buf)
+    }
+    fn size_hint(&self) -> usize {
+        std::mem::size_of::<i64>()
+    }
+}
+
+impl<'r> Decode<'r, MySql> for Monotonic {
+    fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        let ns: i64 = Decode::<MySql>::decode(value)?;
+        Ok(ns.try_into()?)
+    }
+}
+
+// Nanos
+
+macro_rules! impl_mysql_nanos {
+    ($t: ty) => {
+        impl Type<MySql> for Nanos<$t> {
+            fn type_info() -> MySqlTypeInfo {
+                <i64 as Type<MySql>>::type_info()
+            }
+            fn compatible(ty: &MySqlTypeInfo) -> bool {
+                is_integer(ty)
+            }
+        }
+
+        impl Encode<'_, MySql> for Nanos<$t> {
+            fn encode_by_ref(&self, buf: &mut Vec<u8>) -> ResultIsNull {
+                Encode::<MySql>::encode(i64::try_from(self.0)?, buf)
+            }
+            fn size_hint(&self) -> usize {
+                std::mem::size_of::<i64>()
+            }
+        }
+
+        impl<'r> Decode<'r, MySql> for Nanos<$t> {
+            fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+                let ns: i64 = Decode::<MySql>::decode(value)?;
+                Ok(Self(ns.try_into()?))
+            }
+        }
+    };
+}
+
+impl_mysql_nanos!(Timestamp);
+impl_mysql_nanos!(Monotonic);
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use sqlx::{
+        Encode, MySql, Type, TypeInfo,
+        encode::IsNull,
+        types::chrono::{DateTime, NaiveDate, NaiveDateTime, Utc},
+    };
+
+    use super::timestamp_from_datetime;
+    use crate::{Monotonic, Timestamp, rfc3339, sql::Nanos};
+
+    fn encode<'q, T: Encode<'q, MySql>>(value: T) -> Vec<u8> {
+        let mut buf = Vec::new();
+        assert!(matches!(
+            Encode::<MySql>::encode(value, &mut buf).unwrap(),
+            IsNull::No
+        ));
+        buf
+    }
+
+    #[test]
+    fn test_mysql_timestamp() {
+        assert_eq!(<Timestamp as Type<MySql>>::type_info().name(), "DATETIME");
+        let mut expected = vec![11];
+        expected.extend(2024_u16.to_le_bytes());
+        expected.extend([4, 13, 17, 2, 27]);
+        expected.extend(365_001_u32.to_le_bytes());
+        assert_eq!(
+            encode(Timestamp::from_nanos(1_713_027_747_365_001_999)),
+            expected
+        );
+        let mut expected = vec![7];
+        expected.extend(2024_u16.to_le_bytes());
+        expected.extend([4, 13, 17, 2, 27]);
+        assert_eq!(encode(Timestamp::from_secs(1_713_027_747)), expected);
+        let mut buf = Vec::new();
+        assert!(Encode::<MySql>::encode(Timestamp::from_secs(u64::MAX), &mut buf).is_err());
+        let max = Timestamp::from_secs(rfc3339::MAX_SECS);
+        assert_eq!(encode(max)[0], 7);
+        let mut buf = Vec::new();
+        assert!(Encode::<MySql>::encode(max + Duration::from_secs(1), &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_mysql_timestamp_decode() {
+        for ty in [
+            <NaiveDateTime as Type<MySql>>::type_info(),
+            <DateTime<Utc> as Type<MySql>>::type_info(),
+        ] {
+            assert!(<Timestamp as Type<MySql>>::compatible(&ty));
+        }
+        // sqlx decodes binary DATETIME values of 4 (date), 7 (date and time) and 11 (with
+        // microseconds) bytes, zero-length values (0000-00-00) are NULL
+        let date = NaiveDate::from_ymd_opt(2024, 4, 13).unwrap();
+        for (dt, expected) in [
+            (
+                date.and_hms_opt(0, 0, 0),
+                Timestamp::from_secs(1_712_966_400),
+            ),
+            (
+                date.and_hms_opt(17, 2, 27),
+                Timestamp::from_secs(1_713_027_747),
+            ),
+            (
+                date.and_hms_micro_opt(17, 2, 27, 365_001),
+                Timestamp::from_micros(1_713_027_747_365_001),
+            ),
+        ] {
+            assert_eq!(
+                timestamp_from_datetime(dt.unwrap().and_utc()).unwrap(),
+                expected
+            );
+        }
+        let dt = NaiveDate::from_ymd_opt(1969, 12, 31)
+            .unwrap()
+            .and_hms_opt(23, 59, 59)
+            .unwrap();
+        assert!(timestamp_from_datetime(dt.and_utc()).is_err());
+    }
+
+    #[test]
+    fn test_mysql_bigint() {
+        assert_eq!(<Monotonic as Type<MySql>>::type_info().name(), "BIGINT");
+        assert_eq!(
+            encode(Monotonic::from_nanos(2_500_000_000)),
+            2_500_000_000_i64.to_le_bytes()
+        );
+        assert_eq!(
+            encode(Nanos(Timestamp::from_nanos(1_713_027_747_365_000_001))),
+            1_713_027_747_365_000_001_i64.to_le_bytes()
+        );
+        let mut buf = Vec::new();
+        assert!(
+            Encode::<MySql>::encode(Nanos(Timestamp::from_secs(10_000_000_000)), &mut buf).is_err()
+        );
+    }
+
+    #[test]
+    fn test_mysql_bigint_decode() {
+        for ty in [
+            <i64 as Type<MySql>>::type_info(),
+            <u64 as Type<MySql>>::type_info(),
+        ] {
+            assert!(<Monotonic as Type<MySql>>::compatible(&ty));
+            assert!(<Nanos<Timestamp> as Type<MySql>>::compatible(&ty));
+            assert!(!<Timestamp as Type<MySql>>::compatible(&ty));
+        }
+        assert!(!<Monotonic as Type<MySql>>::compatible(
+            &<NaiveDateTime as Type<MySql>>::type_info()
+        ));
+        // binary BIGINT values are little-endian i64
+        let bytes: [u8; 8] = encode(Monotonic::from_nanos(2_500_000_000))
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            Monotonic::try_from(i64::from_le_bytes(bytes)).unwrap(),
+            Monotonic::from_nanos(2_500_000_000)
+        );
+        assert!(Monotonic::try_from(-1_i64).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use crate::{Error, Monotonic, Timestamp, common::NANOS_PER_SEC};
+
+macro_rules! impl_key {
+    ($t: ty) => {
+        impl $t {
+            /// Length of key bytes
+            pub const KEY_LEN: usize = 12;
+            /// Encodes the value as fixed-width key bytes (big-endian `u64` seconds and `u32`
+            /// nanoseconds), the lexicographic order of keys matches the time order
+            ///
+            /// Suitable for key prefixes in ordered KV stores (RocksDB, sled, LMDB etc.)
+            pub fn to_key_bytes(self) -> [u8; Self::KEY_LEN] {
+                let mut key = [0; Self::KEY_LEN];
+                key[..8].copy_from_slice(&self.0.as_secs().to_be_bytes());
+                key[8..].copy_from_slice(&self.0.subsec_nanos().to_be_bytes());
+                key
+            }
+            /// Encodes the value as fixed-width key bytes in descending order (newest first)
+            pub fn to_key_bytes_desc(self) -> [u8; Self::KEY_LEN] {
+                self.to_key_bytes().map(|b| !b)
+            }
+            /// Decodes a value from key bytes, extra bytes after the first [`Self::KEY_LEN`]
+            /// ones (the rest of a key) are ignored
+            pub fn from_key_bytes(key: &[u8]) -> Result<Self, Error> {
+                let key: [u8; Self::KEY_LEN] = key
+                    .get(..Self::KEY_LEN)
+                    .and_then(|k| k.try_into().ok())
+                    .ok_or_else(|| {
+                        Error::Decode(format!(
+                            "key too short: {} byte(s), {} required",
+                            key.len(),
+                            Self::KEY_LEN
+                        ))
+                    })?;
+                let mut secs = [0; 8];
+                secs.copy_from_slice(&key[..8]);
+                let mut nanos = [0; 4];
+                nanos.copy_from_slice(&key[8..]);
+                let nanos = u32::from_be_bytes(nanos);
+                if nanos >= NANOS_PER_SEC {
+                    return Err(Error::Decode(format!(
+                        "key nanoseconds {} out of range",
+                        nanos
+                    )));
+                }
+                Ok(Self(Duration::new(u64::from_be_bytes(secs), nanos)))
+            }
+            /// Decodes a value from descending-order key bytes
+            pub fn from_key_bytes_desc(key: &[u8]) -> Result<Self, Error> {
+                let mut inverted = [0; Self::KEY_LEN];
+                for (i, b) in inverted.iter_mut().zip(key) {
+                    *i = !b;
+                }
+                Self::from_key_bytes(&inverted[..key.len().min(Self::KEY_LEN)])
+            }
+        }
+    };
+}
+
+impl_key!(Timestamp);
+impl_key!(Monotonic);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Monotonic, Timestamp};
+
+    #[test]
+    fn test_key_order() {
+        let values = [
+            Timestamp::from_nanos(0),
+            Timestamp::from_nanos(255),
+            Timestamp::from_nanos(256),
+            Timestamp::from_nanos(999_999_999),
+            Timestamp::from_secs(1),
+            Timestamp::from_nanos(1_713_027_747_365_000_001),
+            Timestamp::from_secs(u64::MAX),
+        ];
+        for pair in values.windows(2) {
+            assert!(pair[0].to_key_bytes() < pair[1].to_key_bytes());
+            assert!(pair[0].to_key_bytes_desc() > pair[1].to_key_bytes_desc());
+        }
+        for ts in values {
+            assert_eq!(Timestamp::from_key_bytes(&ts.to_key_bytes()).unwrap(), ts);
+            assert_eq!(
+                Timestamp::from_key_bytes_desc(&ts.to_key_bytes_desc()).unwrap(),
+                ts
+            );
+        }
+    }
+
+    #[test]
+    fn test_key_prefix() {
+        let mono = Monotonic::from_nanos(2_500_000_000);
+        let mut key = mono.to_key_bytes().to_vec();
+        key.extend(b"/sensor1");
+        assert_eq!(Monotonic::from_key_bytes(&key).unwrap(), mono);
+        assert!(Monotonic::from_key_bytes(&key[..11]).is_err());
+        assert!(Monotonic::from_key_bytes(&[0xff; 12]).is_err());
+        let mut key = mono.to_key_bytes_desc().to_vec();
+        key.extend(b"/sensor1");
+        assert_eq!(Monotonic::from_key_bytes_desc(&key).unwrap(), mono);
+        assert!(Monotonic::from_key_bytes_desc(&key[..11]).is_err());
+    }
+}
@@ -1,21 +1,73 @@
 #![ doc = include_str!( concat!( env!( "CARGO_MANIFEST_DIR" ), "/", "README.md" ) ) ]
+pub use epoch::{EpochAuto, EpochUnit, EpochWindow, Rounding};
 pub use error::Error;
+#[cfg(feature = "rkyv")]
+pub use monotonic::ArchivedMonotonic;
 pub use monotonic::Monotonic;
+#[cfg(feature = "rkyv")]
+pub use timestamp::ArchivedTimestamp;
 pub use timestamp::Timestamp;
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "bincode")]
+pub mod bincode_helpers;
+#[cfg(feature = "cbor")]
+pub mod cbor;
 mod common;
+pub mod compress;
 mod convert;
+mod epoch;
 mod error;
 #[cfg(feature = "bincode")]
 mod impl_bincode;
+#[cfg(feature = "bson")]
+mod impl_bson;
 #[cfg(feature = "chrono")]
 mod impl_chrono;
+#[cfg(any(feature = "diesel-postgres", feature = "diesel-sqlite"))]
+mod impl_diesel;
+#[cfg(feature = "prost")]
+mod impl_prost;
+#[cfg(feature = "redis")]
+mod impl_redis;
+#[cfg(feature = "rkyv")]
+mod impl_rkyv;
+#[cfg(feature = "rusqlite")]
+mod impl_rusqlite;
 #[cfg(feature = "serde")]
 mod impl_serde;
 #[cfg(feature = "sqlx")]
 mod impl_sqlx;
+#[cfg(feature = "sqlx-mysql")]
+mod impl_sqlx_mysql;
+mod key;
 mod monotonic;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
 mod operations;
+#[cfg(any(feature = "bytemuck", feature = "zerocopy"))]
+pub mod pod;
+#[cfg(any(
+    feature = "prost",
+    feature = "cbor",
+    feature = "sqlx",
+    feature = "rusqlite",
+    feature = "diesel-sqlite"
+))]
+mod rfc3339;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+#[cfg(any(feature = "sqlx", feature = "rusqlite"))]
+pub mod sql;
+#[cfg(any(
+    feature = "sqlx",
+    feature = "rusqlite",
+    feature = "diesel-postgres",
+    feature = "diesel-sqlite"
+))]
+mod sql_common;
 #[cfg(test)]
 mod tests;
 mod timestamp;
+pub mod varint;
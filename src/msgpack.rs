@@ -0,0 +1,253 @@
+//! MessagePack timestamp extension type (-1) support for
+//! [rmp-serde](https://crates.io/crates/rmp-serde)
+//!
+//! The module can be used with `#[serde(with = "bma_ts::msgpack")]`. The shortest form (32, 64 or
+//! 96-bit) is chosen automatically on encoding, all three are accepted on decoding.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bma_ts::Timestamp;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!     #[serde(with = "bma_ts::msgpack")]
+//!     t: Timestamp,
+//! }
+//!
+//! let event = Event { t: Timestamp::from_secs(1_713_027_747) };
+//! let packed = rmp_serde::to_vec(&event).unwrap();
+//! let unpacked: Event = rmp_serde::from_slice(&packed).unwrap();
+//! assert_eq!(unpacked.t, event.t);
+//! ```
+use core::fmt;
+use std::time::Duration;
+
+use rmp_serde::MSGPACK_EXT_STRUCT_NAME;
+use serde::{Deserializer, Serialize, Serializer, de};
+
+use crate::{Error, Timestamp, common::NANOS_PER_SEC};
+
+/// MessagePack timestamp extension type
+pub const EXT_TYPE: i8 = -1;
+
+impl Timestamp {
+    /// Encodes the timestamp as MessagePack timestamp extension data (with no ext header), using
+    /// the shortest possible form
+    pub fn try_to_msgpack_ext(self) -> Result<Vec<u8>, Error> {
+        let secs = self.0.as_secs();
+        let nanos = self.0.subsec_nanos();
+        if nanos == 0 {
+            if let Ok(secs) = u32::try_from(secs) {
+                // timestamp 32
+                return Ok(secs.to_be_bytes().to_vec());
+            }
+        }
+        if secs >> 34 == 0 {
+            // timestamp 64
+            return Ok(((u64::from(nanos) << 34) | secs).to_be_bytes().to_vec());
+        }
+        // timestamp 96
+        let secs = i64::try_from(secs)?;
+        let mut data = Vec::with_capacity(12);
+        data.extend(nanos.to_be_bytes());
+        data.extend(secs.to_be_bytes());
+        Ok(data)
+    }
+    /// Decodes MessagePack timestamp extension data (with no ext header) in any of 32, 64 or
+    /// 96-bit forms
+    pub fn try_from_msgpack_ext(data: &[u8]) -> Result<Self, Error> {
+        let (secs, nanos) = match data.len() {
+            4 => (from_be(data), 0),
+            8 => {
+                let v = from_be(data);
+                #[allow(clippy::cast_possible_truncation)]
+                (v & 0x0003_ffff_ffff, (v >> 34) as u32)
+            }
+            12 => {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                let (nanos, secs) = (from_be(&data[..4]) as u32, from_be(&data[4..]) as i64);
+                let secs = u64::try_from(secs).map_err(|_| {
+                    Error::Convert(format!(
+                        "msgpack timestamp {}s is before the UNIX epoch",
+                        secs
+                    ))
+                })?;
+                (secs, nanos)
+            }
+            n => {
+                return Err(Error::Convert(format!(
+                    "invalid msgpack timestamp length: {}",
+                    n
+                )));
+            }
+        };
+        if nanos >= NANOS_PER_SEC {
+            return Err(Error::Convert(format!(
+                "msgpack timestamp nanoseconds {} out of range",
+                nanos
+            )));
+        }
+        Ok(Self(Duration::new(secs, nanos)))
+    }
+}
+
+fn from_be(data: &[u8]) -> u64 {
+    data.iter().fold(0, |acc, b| (acc << 8) | u64::from(*b))
+}
+
+struct ExtBytes<'a>(&'a [u8]);
+
+impl Serialize for ExtBytes<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+pub fn serialize<S>(value: &Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let data = value
+        .try_to_msgpack_ext()
+        .map_err(serde::ser::Error::custom)?;
+    serializer.serialize_newtype_struct(MSGPACK_EXT_STRUCT_NAME, &(EXT_TYPE, ExtBytes(&data)))
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_newtype_struct(MSGPACK_EXT_STRUCT_NAME, ExtVisitor)
+}
+
+struct ExtVisitor;
+
+impl<'de> de::Visitor<'de> for ExtVisitor {
+    type Value = Timestamp;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a msgpack timestamp extension")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let tag: i8 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        if tag != EXT_TYPE {
+            return Err(de::Error::custom(format_args!(
+                "invalid msgpack extension type {}, expected {}",
+                tag, EXT_TYPE
+            )));
+        }
+        let data: ExtData = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Timestamp::try_from_msgpack_ext(&data.0).map_err(de::Error::custom)
+    }
+}
+
+struct ExtData(Vec<u8>);
+
+impl<'de> de::Deserialize<'de> for ExtData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(ExtDataVisitor)
+    }
+}
+
+struct ExtDataVisitor;
+
+impl<'de> de::Visitor<'de> for ExtDataVisitor {
+    type Value = ExtData;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("msgpack extension data")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ExtData(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ExtData(v))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut data = Vec::with_capacity(seq.size_hint().unwrap_or_default().min(12));
+        while let Some(b) = seq.next_element()? {
+            data.push(b);
+        }
+        Ok(ExtData(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::Timestamp;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Event {
+        #[serde(with = "super")]
+        t: Timestamp,
+    }
+
+    #[test]
+    fn test_msgpack_forms() {
+        for (ts, len) in [
+            (Timestamp::from_secs(1_713_027_747), 4),
+            (Timestamp::from_nanos(1_713_027_747_365_000_001), 8),
+            (Timestamp::from_secs(1 << 34), 12),
+            (Timestamp::from_nanos(u64::MAX), 12),
+        ] {
+            let data = ts.try_to_msgpack_ext().unwrap();
+            assert_eq!(data.len(), len);
+            assert_eq!(Timestamp::try_from_msgpack_ext(&data).unwrap(), ts);
+        }
+        assert!(Timestamp::try_from_msgpack_ext(&[0; 5]).is_err());
+        let mut negative = [0xff; 12];
+        negative[..4].copy_from_slice(&[0; 4]);
+        assert!(Timestamp::try_from_msgpack_ext(&negative).is_err());
+    }
+
+    #[test]
+    fn test_msgpack_serde() {
+        let event = Event {
+            t: Timestamp::from_nanos(1_713_027_747_365_000_001),
+        };
+        let packed = rmp_serde::to_vec(&event).unwrap();
+        // fixarray(1), fixext8, type -1
+        assert_eq!(&packed[..3], &[0x91, 0xd7, 0xff]);
+        let unpacked: Event = rmp_serde::from_slice(&packed).unwrap();
+        assert_eq!(unpacked, event);
+        let packed = rmp_serde::to_vec(&(1_u8,)).unwrap();
+        assert!(rmp_serde::from_slice::<Event>(&packed).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+//! Fixed-layout representations of timestamps and monotonic times, suitable for shared memory
+//! and casting raw byte buffers with [bytemuck](https://crates.io/crates/bytemuck) or
+//! [zerocopy](https://crates.io/crates/zerocopy)
+//!
+//! All fields are stored as little-endian byte arrays, so the types have no padding, the
+//! alignment of 1 and the same layout on all platforms.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bma_ts::{Timestamp, pod::NanosLe};
+//!
+//! let ts = Timestamp::from_nanos(1_713_027_747_365_000_001);
+//! let raw = NanosLe::try_from(ts).unwrap();
+//! # #[cfg(feature = "bytemuck")]
+//! # {
+//! let bytes: [u8; 8] = bytemuck::cast(raw);
+//! assert_eq!(bytes, 1_713_027_747_365_000_001_u64.to_le_bytes());
+//! let raw: NanosLe = bytemuck::cast(bytes);
+//! assert_eq!(Timestamp::from(raw), ts);
+//! # }
+//! ```
+use std::time::Duration;
+
+use crate::{Error, Monotonic, Timestamp, common::NANOS_PER_SEC};
+
+/// Nanoseconds as a little-endian `u64` (8 bytes)
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::Immutable,
+        zerocopy::KnownLayout,
+        zerocopy::Unaligned
+    )
+)]
+pub struct NanosLe([u8; 8]);
+
+impl NanosLe {
+    #[inline]
+    pub fn new(nanos: u64) -> Self {
+        Self(nanos.to_le_bytes())
+    }
+    #[inline]
+    pub fn nanos(self) -> u64 {
+        u64::from_le_bytes(self.0)
+    }
+}
+
+/// Seconds as a little-endian `i64` and sub-second nanoseconds as a little-endian `u32`, followed
+/// by 4 reserved bytes (16 bytes)
+///
+/// The reserved bytes are written as zeros and ignored on reading.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::Immutable,
+        zerocopy::KnownLayout,
+        zerocopy::Unaligned
+    )
+)]
+pub struct SecsNanosLe {
+    secs: [u8; 8],
+    nanos: [u8; 4],
+    reserved: [u8; 4],
+}
+
+impl SecsNanosLe {
+    #[inline]
+    pub fn new(secs: i64, nanos: u32) -> Self {
+        Self {
+            secs: secs.to_le_bytes(),
+            nanos: nanos.to_le_bytes(),
+            reserved: [0; 4],
+        }
+    }
+    #[inline]
+    pub fn secs(self) -> i64 {
+        i64::from_le_bytes(self.secs)
+    }
+    #[inline]
+    pub fn subsec_nanos(self) -> u32 {
+        u32::from_le_bytes(self.nanos)
+    }
+}
+
+impl TryFrom<SecsNanosLe> for Duration {
+    type Error = Error;
+    fn try_from(value: SecsNanosLe) -> Result<Self, Self::Error> {
+        let nanos = value.subsec_nanos();
+        if nanos >= NANOS_PER_SEC {
+            return Err(Error::Convert(format!(
+                "nanoseconds {} out of range",
+                nanos
+            )));
+        }
+        let secs = u64::try_from(value.secs())
+            .map_err(|_| Error::Convert(format!("negative seconds {}", value.secs())))?;
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+macro_rules! impl_pod_convert {
+    ($t: ty) => {
+        impl TryFrom<$t> for NanosLe {
+            type Error = Error;
+            #[inline]
+            fn try_from(value: $t) -> Result<Self, Self::Error> {
+                Ok(Self::new(value.try_into()?))
+            }
+        }
+
+        impl From<NanosLe> for $t {
+            #[inline]
+            fn from(value: NanosLe) -> Self {
+                Self::from_nanos(value.nanos())
+            }
+        }
+
+        impl TryFrom<$t> for SecsNanosLe {
+            type Error = Error;
+            #[inline]
+            fn try_from(value: $t) -> Result<Self, Self::Error> {
+                Ok(Self::new(
+                    value.0.as_secs().try_into()?,
+                    value.0.subsec_nanos(),
+                ))
+            }
+        }
+
+        impl TryFrom<SecsNanosLe> for $t {
+            type Error = Error;
+            #[inline]
+            fn try_from(value: SecsNanosLe) -> Result<Self, Self::Error> {
+                Ok(Self(value.try_into()?))
+            }
+        }
+    };
+}
+
+impl_pod_convert!(Timestamp);
+impl_pod_convert!(Monotonic);
+
+#[cfg(test)]
+mod tests {
+    use super::{NanosLe, SecsNanosLe};
+    use crate::{Monotonic, Timestamp};
+
+    #[test]
+    fn test_pod_layout() {
+        assert_eq!(std::mem::size_of::<NanosLe>(), 8);
+        assert_eq!(std::mem::align_of::<NanosLe>(), 1);
+        assert_eq!(std::mem::size_of::<SecsNanosLe>(), 16);
+        assert_eq!(std::mem::align_of::<SecsNanosLe>(), 1);
+    }
+
+    #[test]
+    fn test_pod_convert() {
+        let ts = Timestamp::from_nanos(1_713_027_747_365_000_001);
+        assert_eq!(Timestamp::from(NanosLe::try_from(ts).unwrap()), ts);
+        let raw = SecsNanosLe::try_from(ts).unwrap();
+        assert_eq!(
+            (raw.secs(), raw.subsec_nanos()),
+            (1_713_027_747, 365_000_001)
+        );
+        assert_eq!(Timestamp::try_from(raw).unwrap(), ts);
+        let mono = Monotonic::from_secs(u64::MAX);
+        assert!(NanosLe::try_from(mono).is_err());
+        assert!(SecsNanosLe::try_from(mono).is_err());
+        assert!(Monotonic::try_from(SecsNanosLe::new(-1, 0)).is_err());
+        assert!(Monotonic::try_from(SecsNanosLe::new(1, 1_000_000_000)).is_err());
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_pod_bytemuck() {
+        let mut buf = [0_u8; 32];
+        buf[..8].copy_from_slice(&1_713_027_747_i64.to_le_bytes());
+        buf[8..12].copy_from_slice(&5_u32.to_le_bytes());
+        let values: &[SecsNanosLe] = bytemuck::cast_slice(&buf);
+        assert_eq!(values.len(), 2);
+        assert_eq!(
+            Timestamp::try_from(values[0]).unwrap(),
+            Timestamp::from_nanos(1_713_027_747_000_000_005)
+        );
+        assert_eq!(
+            Timestamp::try_from(values[1]).unwrap(),
+            Timestamp::from_secs(0)
+        );
+    }
+
+    #[cfg(feature = "zerocopy")]
+    #[test]
+    fn test_pod_zerocopy() {
+        use zerocopy::{FromBytes, IntoBytes};
+
+        let raw = NanosLe::try_from(Monotonic::from_nanos(42)).unwrap();
+        assert_eq!(raw.as_bytes(), &42_u64.to_le_bytes());
+        let buf = [1_u8, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0];
+        let values = <[NanosLe]>::ref_from_bytes(&buf).unwrap();
+        assert_eq!(Monotonic::from(values[1]), Monotonic::from_nanos(2));
+    }
+}
@@ -0,0 +1,289 @@
+//! Minimal RFC 3339 formatting/parsing for UTC times, used by integrations which require textual
+//! dates without depending on chrono
+//!
+//! Parsing is available for all the consumers of the module, formatting (with the civil date
+//! conversion it requires) only for prost, cbor and the SQLite text representation.
+use std::time::Duration;
+
+use crate::{Error, common::NANOS_PER_SEC};
+
+#[cfg(any(
+    feature = "prost",
+    feature = "cbor",
+    feature = "sqlx",
+    feature = "rusqlite"
+))]
+pub(crate) use format::*;
+
+/// Days since 1970-01-01 for a proleptic Gregorian date
+#[allow(clippy::similar_names)]
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = i64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn digits(&mut self, n: usize) -> Option<u32> {
+        let v = self.s.get(self.pos..self.pos + n)?;
+        let mut result = 0;
+        for c in v {
+            if !c.is_ascii_digit() {
+                return None;
+            }
+            result = result * 10 + u32::from(c - b'0');
+        }
+        self.pos += n;
+        Some(result)
+    }
+    fn expect(&mut self, f: impl Fn(u8) -> bool) -> Option<u8> {
+        let c = *self.s.get(self.pos)?;
+        if f(c) {
+            self.pos += 1;
+            Some(c)
+        } else {
+            None
+        }
+    }
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+}
+
+/// Parses `YYYY-MM-DD[T ]HH:MM:SS[.f{1,9}][Z|+HH:MM|-HH:MM]`, times with no offset are
+/// considered as UTC
+pub(crate) fn parse(s: &str) -> Result<Duration, Error> {
+    parse_opt(s.trim()).ok_or_else(|| Error::Parse(format!("invalid RFC 3339 time: {}", s)))?
+}
+
+#[allow(clippy::cast_sign_loss)]
+fn parse_opt(s: &str) -> Option<Result<Duration, Error>> {
+    let mut p = Parser {
+        s: s.as_bytes(),
+        pos: 0,
+    };
+    let year = p.digits(4)?;
+    p.expect(|c| c == b'-')?;
+    let month = p.digits(2)?;
+    p.expect(|c| c == b'-')?;
+    let day = p.digits(2)?;
+    p.expect(|c| c == b'T' || c == b't' || c == b' ')?;
+    let hour = p.digits(2)?;
+    p.expect(|c| c == b':')?;
+    let min = p.digits(2)?;
+    p.expect(|c| c == b':')?;
+    let sec = p.digits(2)?;
+    let mut nanos = 0;
+    if p.expect(|c| c == b'.').is_some() {
+        let mut n = 0;
+        while let Some(d) = p.expect(|c| c.is_ascii_digit()) {
+            n += 1;
+            if n > 9 {
+                return None;
+            }
+            nanos = nanos * 10 + u32::from(d - b'0');
+        }
+        if n == 0 {
+            return None;
+        }
+        nanos *= 10_u32.pow(9 - n);
+    }
+    let offset: i64 = match p.peek() {
+        None => 0,
+        Some(b'Z' | b'z') => {
+            p.pos += 1;
+            0
+        }
+        Some(c @ (b'+' | b'-')) => {
+            p.pos += 1;
+            let oh = p.digits(2)?;
+            p.expect(|c| c == b':')?;
+            let om = p.digits(2)?;
+            if oh > 23 || om > 59 {
+                return None;
+            }
+            let offset = i64::from(oh * 3600 + om * 60);
+            if c == b'-' { -offset } else { offset }
+        }
+        Some(_) => return None,
+    };
+    if p.pos != p.s.len()
+        || !(1..=12).contains(&month)
+        || day == 0
+        || day > days_in_month(year, month)
+        || hour > 23
+        || min > 59
+        // leap seconds are not supported
+        || sec > 59
+    {
+        return None;
+    }
+    let secs = days_from_civil(i64::from(year), month, day) * 86_400
+        + i64::from(hour * 3600 + min * 60 + sec)
+        - offset;
+    if secs < 0 {
+        return Some(Err(Error::Convert(format!(
+            "time {} is before the UNIX epoch",
+            s
+        ))));
+    }
+    debug_assert!(nanos < NANOS_PER_SEC);
+    Some(Ok(Duration::new(secs as u64, nanos)))
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(any(
+    feature = "prost",
+    feature = "cbor",
+    feature = "sqlx",
+    feature = "rusqlite"
+))]
+mod format {
+    use std::time::Duration;
+
+    #[cfg(any(feature = "prost", feature = "cbor"))]
+    use crate::Error;
+
+    const SECS_PER_DAY: u64 = 86_400;
+    /// 9999-12-31T23:59:59Z
+    pub(crate) const MAX_SECS: u64 = 253_402_300_799;
+
+    /// Proleptic Gregorian date (year, month, day) for days since 1970-01-01
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::similar_names
+    )]
+    pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        (year, month, day)
+    }
+
+    /// Date/time fields of a UTC time: (year, month, day, hour, minute, second)
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub(crate) fn to_civil(d: Duration) -> (i64, u32, u32, u32, u32, u32) {
+        let secs = d.as_secs();
+        let (year, month, day) = civil_from_days((secs / SECS_PER_DAY) as i64);
+        let sod = (secs % SECS_PER_DAY) as u32;
+        (year, month, day, sod / 3600, sod / 60 % 60, sod % 60)
+    }
+
+    /// Formats a UTC time as `YYYY-MM-DDTHH:MM:SS[.fff]Z`, with 0, 3, 6 or 9 fraction digits, as
+    /// required by the protobuf JSON mapping
+    #[cfg(any(feature = "prost", feature = "cbor"))]
+    pub(crate) fn format(d: Duration) -> Result<String, Error> {
+        if d.as_secs() > MAX_SECS {
+            return Err(Error::Convert(format!(
+                "time {} is after 9999-12-31T23:59:59Z",
+                d.as_nanos()
+            )));
+        }
+        let (year, month, day, hour, min, sec) = to_civil(d);
+        Ok(format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z",
+            year,
+            month,
+            day,
+            hour,
+            min,
+            sec,
+            format_fraction(d.subsec_nanos())
+        ))
+    }
+
+    /// Formats sub-second nanoseconds with 0, 3, 6 or 9 digits
+    #[cfg(any(feature = "prost", feature = "cbor"))]
+    pub(crate) fn format_fraction(nanos: u32) -> String {
+        if nanos == 0 {
+            String::new()
+        } else if nanos % 1_000_000 == 0 {
+            format!(".{:03}", nanos / 1_000_000)
+        } else if nanos % 1_000 == 0 {
+            format!(".{:06}", nanos / 1_000)
+        } else {
+            format!(".{:09}", nanos)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::time::Duration;
+
+        use super::*;
+        use crate::rfc3339::days_from_civil;
+
+        #[test]
+        fn test_civil() {
+            assert_eq!(days_from_civil(1970, 1, 1), 0);
+            assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+            assert_eq!(days_from_civil(1, 1, 1), -719_162);
+            for days in [-719_162, -1, 0, 59, 11_016, 11_017, 2_932_896] {
+                let (y, m, d) = civil_from_days(days);
+                assert_eq!(days_from_civil(y, m, d), days);
+            }
+            assert_eq!(
+                to_civil(Duration::from_secs(MAX_SECS)),
+                (9999, 12, 31, 23, 59, 59)
+            );
+        }
+
+        #[cfg(any(feature = "prost", feature = "cbor"))]
+        #[test]
+        fn test_format() {
+            let d = Duration::new(1_713_027_747, 365_000_000);
+            assert_eq!(format(d).unwrap(), "2024-04-13T17:02:27.365Z");
+            assert_eq!(
+                format(Duration::new(0, 1)).unwrap(),
+                "1970-01-01T00:00:00.000000001Z"
+            );
+            assert_eq!(
+                format(Duration::new(MAX_SECS, 0)).unwrap(),
+                "9999-12-31T23:59:59Z"
+            );
+            assert!(format(Duration::new(MAX_SECS + 1, 0)).is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let d = Duration::new(1_713_027_747, 365_000_000);
+        assert_eq!(parse("2024-04-13T17:02:27.365Z").unwrap(), d);
+        assert_eq!(parse("2024-04-13 20:02:27.365+03:00").unwrap(), d);
+        assert_eq!(parse("2024-04-13t17:02:27.365").unwrap(), d);
+        assert!(parse("1969-12-31T23:59:59Z").is_err());
+        assert!(parse("2023-02-29T00:00:00Z").is_err());
+        assert!(parse("2024-04-13T17:02:27.Z").is_err());
+        assert!(parse("2024-04-13T17:02:27.1234567891Z").is_err());
+        assert!(parse("2024-04-13").is_err());
+    }
+}
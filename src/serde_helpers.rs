@@ -0,0 +1,334 @@
+//! Helpers for `#[serde(with = "...")]` and `#[serde(deserialize_with = "...")]` attributes
+use core::fmt;
+
+use serde::{Deserializer, de};
+
+use crate::{EpochWindow, Timestamp};
+
+/// Deserializes numeric timestamps in seconds, milliseconds, microseconds or nanoseconds,
+/// inferring the unit from the value magnitude with the default [`EpochWindow`]
+///
+/// Non-numeric strings are parsed as usual. Serialization is the default one, so values outside
+/// the window (e.g. `Timestamp::from_secs(0)`) are serialized but can not be deserialized back.
+///
+/// # Example
+///
+/// ```rust
+/// use bma_ts::Timestamp;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Event {
+///     #[serde(with = "bma_ts::serde_helpers::epoch_auto")]
+///     t: Timestamp,
+/// }
+/// ```
+pub mod epoch_auto {
+    use serde::{Deserializer, Serialize, Serializer};
+
+    use crate::{EpochWindow, Timestamp};
+
+    pub fn serialize<S>(value: &Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        EpochWindow::default().deserialize(deserializer)
+    }
+}
+
+/// Serializes timestamps as `{ "secs_since_epoch", "nanos_since_epoch" }` maps, the same way as
+/// serde serializes `SystemTime`
+///
+/// Allows to replace `SystemTime` fields with [`Timestamp`] with no data migration. The default
+/// deserializer accepts the map form as well.
+pub mod system_time {
+    use std::time::Duration;
+
+    use serde::ser::SerializeStruct;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<Duration>,
+        S: Serializer,
+    {
+        let d = value.as_ref();
+        let mut state = serializer.serialize_struct("SystemTime", 2)?;
+        state.serialize_field("secs_since_epoch", &d.as_secs())?;
+        state.serialize_field("nanos_since_epoch", &d.subsec_nanos())?;
+        state.end()
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+/// Serializes timestamps and monotonic times as `{ "secs", "nanos" }` maps, the same way as serde
+/// serializes `Duration`
+///
+/// The default deserializer accepts the map form as well.
+pub mod secs_nanos {
+    use std::time::Duration;
+
+    use serde::ser::SerializeStruct;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<Duration>,
+        S: Serializer,
+    {
+        let d = value.as_ref();
+        let mut state = serializer.serialize_struct("Duration", 2)?;
+        state.serialize_field("secs", &d.as_secs())?;
+        state.serialize_field("nanos", &d.subsec_nanos())?;
+        state.end()
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+/// Serializes values as `[secs, nanos]` pairs, also accepted by the default deserializer (see
+/// [full range](crate#full-range))
+pub mod full_range {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<Duration>,
+        S: Serializer,
+    {
+        let d = value.as_ref();
+        (d.as_secs(), d.subsec_nanos()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+/// Serializes timestamps as BSON dates (`$date` in extended JSON), sub-millisecond precision is
+/// truncated
+///
+/// Allows to use [`Timestamp`] fields with MongoDB date queries and TTL indexes.
+///
+/// # Example
+///
+/// ```rust
+/// use bma_ts::Timestamp;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Event {
+///     #[serde(with = "bma_ts::serde_helpers::bson_datetime")]
+///     t: Timestamp,
+/// }
+///
+/// let doc = bson::to_document(&Event { t: Timestamp::from_millis(1_713_027_747_365) }).unwrap();
+/// assert!(matches!(doc.get("t"), Some(bson::Bson::DateTime(_))));
+/// ```
+#[cfg(feature = "bson")]
+pub mod bson_datetime {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::Timestamp;
+
+    pub fn serialize<S>(value: &Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        bson::DateTime::try_from(*value)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        bson::DateTime::deserialize(deserializer)?
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl EpochWindow {
+    /// Deserializes a timestamp, inferring the unit of numeric values with the window
+    ///
+    /// Can be used to build custom `deserialize_with` functions for non-default windows
+    pub fn deserialize<'de, D>(self, deserializer: D) -> Result<Timestamp, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(EpochAutoVisitor(self))
+    }
+}
+
+struct EpochAutoVisitor(EpochWindow);
+
+impl de::Visitor<'_> for EpochAutoVisitor {
+    type Value = Timestamp;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .write_str("an epoch timestamp in seconds, milliseconds, microseconds or nanoseconds")
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.0.timestamp(value).map_err(de::Error::custom)
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let value = u64::try_from(value)
+            .map_err(|_| de::Error::invalid_value(de::Unexpected::Signed(value), &self))?;
+        self.visit_u64(value)
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.0.timestamp_f64(value).map_err(de::Error::custom)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if value.trim().parse::<f64>().is_ok() {
+            self.0.parse(value)
+        } else {
+            value.parse()
+        }
+        .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{Monotonic, Timestamp};
+
+    #[derive(Deserialize)]
+    struct Event {
+        #[serde(with = "super::epoch_auto")]
+        t: Timestamp,
+    }
+
+    #[test]
+    fn test_epoch_auto_deserialize() {
+        for s in [
+            r#"{"t":1713027747365}"#,
+            r#"{"t":1713027747365000000}"#,
+            r#"{"t":"1713027747365000"}"#,
+        ] {
+            let event: Event = serde_json::from_str(s).unwrap();
+            assert_eq!(event.t, Timestamp::from_millis(1_713_027_747_365));
+        }
+        let event: Event = serde_json::from_str(r#"{"t":1713027747.5}"#).unwrap();
+        assert_eq!(event.t, Timestamp::from_millis(1_713_027_747_500));
+        assert!(serde_json::from_str::<Event>(r#"{"t":1713027}"#).is_err());
+        assert!(serde_json::from_str::<Event>(r#"{"t":-1713027747}"#).is_err());
+        // values outside the window do not round-trip
+        assert!(serde_json::from_str::<Event>(r#"{"t":0}"#).is_err());
+    }
+
+    #[cfg(feature = "bson")]
+    #[test]
+    fn test_bson_datetime() {
+        #[derive(Serialize, Deserialize)]
+        struct Event {
+            #[serde(with = "super::bson_datetime")]
+            t: Timestamp,
+        }
+        let event = Event {
+            t: Timestamp::from_millis(1_713_027_747_365),
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"t":{"$date":{"$numberLong":"1713027747365"}}}"#
+        );
+        let doc = bson::to_document(&event).unwrap();
+        assert_eq!(
+            doc.get_datetime("t").unwrap().timestamp_millis(),
+            1_713_027_747_365
+        );
+        let event: Event = bson::from_document(doc).unwrap();
+        assert_eq!(event.t, Timestamp::from_millis(1_713_027_747_365));
+    }
+
+    #[test]
+    fn test_system_time_compat() {
+        #[derive(Serialize, Deserialize)]
+        struct Old {
+            t: std::time::SystemTime,
+            d: std::time::Duration,
+        }
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct New {
+            #[serde(with = "super::system_time")]
+            t: Timestamp,
+            #[serde(with = "super::secs_nanos")]
+            d: Monotonic,
+        }
+        let old = Old {
+            t: std::time::UNIX_EPOCH + std::time::Duration::new(1_713_027_747, 365),
+            d: std::time::Duration::new(5, 6),
+        };
+        let persisted = serde_json::to_string(&old).unwrap();
+        let new: New = serde_json::from_str(&persisted).unwrap();
+        assert_eq!(new.t, Timestamp::from_nanos(1_713_027_747_000_000_365));
+        assert_eq!(new.d, Monotonic::from_nanos(5_000_000_006));
+        assert_eq!(serde_json::to_string(&new).unwrap(), persisted);
+    }
+
+    #[test]
+    fn test_full_range() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Data {
+            #[serde(with = "super::full_range")]
+            t: Timestamp,
+            #[serde(with = "super::full_range")]
+            d: Monotonic,
+        }
+        let data = Data {
+            t: Timestamp::from_nanos(1_713_027_747_000_000_365),
+            d: Monotonic::from_secs(u64::MAX),
+        };
+        let s = serde_json::to_string(&data).unwrap();
+        assert_eq!(s, r#"{"t":[1713027747,365],"d":[18446744073709551615,0]}"#);
+        assert_eq!(serde_json::from_str::<Data>(&s).unwrap(), data);
+        assert!(serde_json::to_string(&data.d).is_err());
+    }
+}
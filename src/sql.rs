@@ -0,0 +1,163 @@
+//! Alternative SQL representations for [sqlx](https://crates.io/crates/sqlx) and
+//! [rusqlite](https://crates.io/crates/rusqlite)
+//!
+//! `Bounded`, `Interval` and `TimestampRange` are Postgres types, available with the **sqlx**
+//! feature only.
+use crate::Timestamp;
+
+#[cfg(feature = "sqlx")]
+pub use postgres::{Bounded, Interval, TimestampRange};
+
+/// Stores the inner value as `BIGINT` nanoseconds (values up to 2262-04-11)
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Nanos<T>(pub T);
+
+/// Stores the inner timestamp as SQLite `TEXT` (`YYYY-MM-DD HH:MM:SS.fffffffff`, UTC), which
+/// can be processed with SQLite date/time functions
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct IsoText(pub Timestamp);
+
+/// Stores the inner timestamp as SQLite `REAL` Julian day (the precision is about 20µs for
+/// current dates)
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct JulianDay(pub Timestamp);
+
+/// Stores the inner timestamp as SQLite `INTEGER` UNIX seconds, sub-second precision is
+/// truncated
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct UnixSecs(pub Timestamp);
+
+/// Stores the inner timestamp as SQLite `INTEGER` UNIX milliseconds, sub-millisecond
+/// precision is truncated
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct UnixMillis(pub Timestamp);
+
+#[cfg(feature = "sqlx")]
+mod postgres {
+    use std::ops::{
+        Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+    };
+
+    use crate::Timestamp;
+
+    /// Stores the inner value ([`Monotonic`](crate::Monotonic), `Duration`) as Postgres `INTERVAL`
+    ///
+    /// Sub-microsecond precision is truncated, intervals are written with zero days and months.
+    /// Intervals with days are read as 24-hour days, ones with months are rejected.
+    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct Interval<T>(pub T);
+
+    /// A range of timestamps, stored as Postgres `TSTZRANGE` (`TSRANGE` columns are compatible)
+    ///
+    /// Empty ranges can not be represented and fail to decode.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bma_ts::{Timestamp, sql::TimestampRange};
+    /// use std::ops::RangeBounds;
+    ///
+    /// let range = TimestampRange::from(Timestamp::from_secs(10)..Timestamp::from_secs(20));
+    /// assert!(range.contains(&Timestamp::from_secs(15)));
+    /// assert!(!range.contains(&Timestamp::from_secs(20)));
+    /// ```
+    #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+    pub struct TimestampRange {
+        pub start: Bound<Timestamp>,
+        pub end: Bound<Timestamp>,
+    }
+
+    impl TimestampRange {
+        #[inline]
+        pub fn new(start: Bound<Timestamp>, end: Bound<Timestamp>) -> Self {
+            Self { start, end }
+        }
+    }
+
+    impl RangeBounds<Timestamp> for TimestampRange {
+        fn start_bound(&self) -> Bound<&Timestamp> {
+            self.start.as_ref()
+        }
+        fn end_bound(&self) -> Bound<&Timestamp> {
+            self.end.as_ref()
+        }
+    }
+
+    impl From<(Bound<Timestamp>, Bound<Timestamp>)> for TimestampRange {
+        #[inline]
+        fn from((start, end): (Bound<Timestamp>, Bound<Timestamp>)) -> Self {
+            Self { start, end }
+        }
+    }
+
+    impl From<Range<Timestamp>> for TimestampRange {
+        #[inline]
+        fn from(r: Range<Timestamp>) -> Self {
+            Self::new(Bound::Included(r.start), Bound::Excluded(r.end))
+        }
+    }
+
+    impl From<RangeInclusive<Timestamp>> for TimestampRange {
+        #[inline]
+        fn from(r: RangeInclusive<Timestamp>) -> Self {
+            let (start, end) = r.into_inner();
+            Self::new(Bound::Included(start), Bound::Included(end))
+        }
+    }
+
+    impl From<RangeFrom<Timestamp>> for TimestampRange {
+        #[inline]
+        fn from(r: RangeFrom<Timestamp>) -> Self {
+            Self::new(Bound::Included(r.start), Bound::Unbounded)
+        }
+    }
+
+    impl From<RangeTo<Timestamp>> for TimestampRange {
+        #[inline]
+        fn from(r: RangeTo<Timestamp>) -> Self {
+            Self::new(Bound::Unbounded, Bound::Excluded(r.end))
+        }
+    }
+
+    impl From<RangeToInclusive<Timestamp>> for TimestampRange {
+        #[inline]
+        fn from(r: RangeToInclusive<Timestamp>) -> Self {
+            Self::new(Bound::Unbounded, Bound::Included(r.end))
+        }
+    }
+
+    impl From<RangeFull> for TimestampRange {
+        #[inline]
+        fn from(_: RangeFull) -> Self {
+            Self::new(Bound::Unbounded, Bound::Unbounded)
+        }
+    }
+
+    /// A timestamp which may be Postgres `infinity` or `-infinity`, stored as `TIMESTAMPTZ`
+    ///
+    /// The default [`Timestamp`] mapping fails to decode infinite values.
+    #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub enum Bounded {
+        NegInfinity,
+        Finite(Timestamp),
+        Infinity,
+    }
+
+    impl Bounded {
+        /// Returns the timestamp if the value is finite
+        #[inline]
+        pub fn finite(self) -> Option<Timestamp> {
+            match self {
+                Bounded::Finite(t) => Some(t),
+                Bounded::NegInfinity | Bounded::Infinity => None,
+            }
+        }
+    }
+
+    impl From<Timestamp> for Bounded {
+        #[inline]
+        fn from(t: Timestamp) -> Self {
+            Bounded::Finite(t)
+        }
+    }
+}
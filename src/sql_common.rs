@@ -0,0 +1,155 @@
+//! Conversions shared by the SQL integrations (sqlx, rusqlite and diesel)
+#[cfg(any(feature = "sqlx", feature = "rusqlite", feature = "diesel-sqlite"))]
+use std::time::Duration;
+
+#[cfg(any(feature = "sqlx", feature = "rusqlite", feature = "diesel-sqlite"))]
+use crate::rfc3339;
+use crate::{Error, Timestamp};
+
+#[cfg(any(feature = "sqlx", feature = "rusqlite", feature = "diesel-sqlite"))]
+const SECS_PER_DAY: f64 = 86_400.0;
+/// Julian day of 1970-01-01T00:00:00Z
+#[cfg(any(feature = "sqlx", feature = "rusqlite", feature = "diesel-sqlite"))]
+const UNIX_EPOCH_JULIAN_DAY: f64 = 2_440_587.5;
+/// Julian day of 10000-01-01T00:00:00Z, REAL values below are considered as Julian days
+#[cfg(any(feature = "sqlx", feature = "rusqlite", feature = "diesel-sqlite"))]
+const MAX_JULIAN_DAY: f64 = 5_373_484.5;
+/// Postgres timestamps are stored as microseconds since 2000-01-01T00:00:00Z
+#[cfg(any(feature = "sqlx", feature = "diesel-postgres"))]
+pub(crate) const J2000_EPOCH_US: i64 = 946_684_800_000_000;
+#[cfg(any(feature = "sqlx", feature = "diesel-postgres"))]
+pub(crate) const PG_INFINITY: i64 = i64::MAX;
+#[cfg(any(feature = "sqlx", feature = "diesel-postgres"))]
+pub(crate) const PG_NEG_INFINITY: i64 = i64::MIN;
+
+/// Formats a timestamp as `YYYY-MM-DD HH:MM:SS.fffffffff` (the SQLite format with fixed 9
+/// fraction digits, so stored values are ordered as text)
+#[cfg(any(feature = "sqlx", feature = "rusqlite"))]
+pub(crate) fn sqlite_text(t: Timestamp) -> Result<String, Error> {
+    if t.0.as_secs() > rfc3339::MAX_SECS {
+        return Err(Error::Convert(format!(
+            "timestamp {} is after 9999-12-31T23:59:59Z",
+            t
+        )));
+    }
+    let (year, month, day, hour, min, sec) = rfc3339::to_civil(t.0);
+    Ok(format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:09}",
+        year,
+        month,
+        day,
+        hour,
+        min,
+        sec,
+        t.0.subsec_nanos()
+    ))
+}
+
+/// Parses ISO 8601 text, written by `sql::IsoText` or SQLite date/time functions (UTC is assumed
+/// if there is no offset)
+#[cfg(any(feature = "sqlx", feature = "rusqlite", feature = "diesel-sqlite"))]
+pub(crate) fn timestamp_from_text(s: &str) -> Result<Timestamp, Error> {
+    let s = s.trim();
+    if s.len() == 10 {
+        // date only
+        rfc3339::parse(&format!("{} 00:00:00", s)).map(Into::into)
+    } else {
+        rfc3339::parse(s).map(Into::into)
+    }
+}
+
+#[cfg(any(feature = "sqlx", feature = "rusqlite"))]
+pub(crate) fn julian_day(t: Timestamp) -> f64 {
+    t.as_secs_f64() / SECS_PER_DAY + UNIX_EPOCH_JULIAN_DAY
+}
+
+#[cfg(any(feature = "sqlx", feature = "rusqlite", feature = "diesel-sqlite"))]
+pub(crate) fn timestamp_from_julian_day(jd: f64) -> Result<Timestamp, Error> {
+    Duration::try_from_secs_f64((jd - UNIX_EPOCH_JULIAN_DAY) * SECS_PER_DAY)
+        .map(Into::into)
+        .map_err(|e| Error::Convert(format!("invalid Julian day {}: {}", jd, e)))
+}
+
+#[cfg(any(feature = "sqlx", feature = "rusqlite", feature = "diesel-sqlite"))]
+pub(crate) fn timestamp_from_secs_f64(secs: f64) -> Result<Timestamp, Error> {
+    Duration::try_from_secs_f64(secs)
+        .map(Into::into)
+        .map_err(|e| Error::Convert(format!("invalid seconds {}: {}", secs, e)))
+}
+
+/// Values below the Julian day of 10000-01-01 are considered as Julian days, others as UNIX
+/// seconds (after 1970-03-04)
+#[cfg(any(feature = "sqlx", feature = "rusqlite", feature = "diesel-sqlite"))]
+pub(crate) fn timestamp_from_real(value: f64) -> Result<Timestamp, Error> {
+    if value < MAX_JULIAN_DAY {
+        timestamp_from_julian_day(value)
+    } else {
+        timestamp_from_secs_f64(value)
+    }
+}
+
+#[cfg(any(feature = "sqlx", feature = "diesel-postgres"))]
+pub(crate) fn pg_micros(t: Timestamp) -> Result<i64, Error> {
+    Ok(i64::try_from(t.as_micros())? - J2000_EPOCH_US)
+}
+
+#[cfg(any(feature = "sqlx", feature = "diesel-postgres"))]
+pub(crate) fn timestamp_from_pg_micros(us: i64) -> Result<Timestamp, Error> {
+    if us == PG_INFINITY || us == PG_NEG_INFINITY {
+        return Err(Error::Convert(
+            "infinite timestamp can not be decoded".to_owned(),
+        ));
+    }
+    let us = us
+        .checked_add(J2000_EPOCH_US)
+        .ok_or_else(|| Error::Convert(format!("timestamp {}us is out of range", us)))?;
+    let us = u64::try_from(us)
+        .map_err(|_| Error::Convert(format!("timestamp {}us is before the UNIX epoch", us)))?;
+    Ok(Timestamp::from_micros(us))
+}
+
+#[cfg(all(test, any(feature = "sqlx", feature = "rusqlite")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqlite_text() {
+        let ts = Timestamp::from_nanos(1_713_027_747_365_000_001);
+        let s = sqlite_text(ts).unwrap();
+        assert_eq!(s, "2024-04-13 17:02:27.365000001");
+        assert_eq!(timestamp_from_text(&s).unwrap(), ts);
+        assert_eq!(
+            timestamp_from_text("2024-04-13 17:02:27").unwrap(),
+            Timestamp::from_secs(1_713_027_747)
+        );
+        assert_eq!(
+            timestamp_from_text("2024-04-13T19:02:27.5+02:00").unwrap(),
+            Timestamp::from_millis(1_713_027_747_500)
+        );
+        assert_eq!(
+            timestamp_from_text("2024-04-13").unwrap(),
+            Timestamp::from_secs(1_712_966_400)
+        );
+        assert!(timestamp_from_text("yesterday").is_err());
+        // text order matches the time order
+        assert!(
+            sqlite_text(Timestamp::from_secs(1_713_027_747)).unwrap()
+                < sqlite_text(Timestamp::from_nanos(1_713_027_747_000_000_001)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_sqlite_real() {
+        let ts = Timestamp::from_secs(1_713_027_747);
+        let jd = julian_day(ts);
+        assert!((jd - 2_460_414.210_034_722).abs() < 1e-6);
+        let decoded = timestamp_from_real(jd).unwrap();
+        assert!((decoded.as_secs_f64() - ts.as_secs_f64()).abs() < 1e-4);
+        assert_eq!(
+            timestamp_from_real(1_713_027_747.5).unwrap(),
+            Timestamp::from_millis(1_713_027_747_500)
+        );
+        assert!(timestamp_from_real(2_440_000.0).is_err());
+        assert!(timestamp_from_real(f64::NAN).is_err());
+    }
+}
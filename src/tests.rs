@@ -29,3 +29,10 @@ fn test_time() {
     assert_eq!(time.as_micros(), timestamp_ms * 1_000);
     assert_eq!(time.as_nanos(), timestamp_ms * 1_000_000);
 }
+
+#[test]
+fn test_timestamptz_lossless() {
+    assert!(!Timestamp::from_nanos(1_713_027_747_365_000_001).is_timestamptz_lossless());
+    assert!(Timestamp::from_micros(1_713_027_747_365_001).is_timestamptz_lossless());
+    assert!(!Timestamp::from_secs(u64::MAX).is_timestamptz_lossless());
+}
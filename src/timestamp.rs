@@ -8,6 +8,26 @@ use web_time::{SystemTime, UNIX_EPOCH};
 use crate::Error;
 
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd))
+)]
+#[cfg_attr(
+    any(feature = "diesel-postgres", feature = "diesel-sqlite"),
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(
+    feature = "diesel-postgres",
+    diesel(
+        sql_type = diesel::sql_types::Timestamptz,
+        sql_type = diesel::sql_types::Timestamp
+    )
+)]
+#[cfg_attr(
+    feature = "diesel-sqlite",
+    diesel(sql_type = diesel::sql_types::BigInt)
+)]
 pub struct Timestamp(pub(crate) Duration);
 
 const ANSI_EPOCH_DIFF_NANOS: u64 = 11_644_473_600_000_000_000;
@@ -75,4 +95,9 @@ impl Timestamp {
                 .ok_or_else(|| Error::Convert("Failed to convert from UNIX to ANSI".to_string()))?,
         )))
     }
+    /// Returns `true` if the timestamp is not changed after being stored as Postgres
+    /// `TIMESTAMPTZ`/`TIMESTAMP` (has no sub-microsecond precision and fits the range)
+    pub fn is_timestamptz_lossless(self) -> bool {
+        self.0.subsec_nanos() % 1_000 == 0 && i64::try_from(self.as_micros()).is_ok()
+    }
 }
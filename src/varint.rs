@@ -0,0 +1,253 @@
+//! Compact LEB128 (varint) encodings of timestamps, monotonic times and signed durations
+//!
+//! Values are encoded as nanoseconds: unsigned ones as LEB128, signed ones as zigzag + LEB128
+//! (up to 19 bytes). Base-relative methods store the signed difference from a reference value,
+//! which takes a few bytes for values close to the base.
+//!
+//! Slice methods return/accept the number of bytes processed, so the values can be packed one
+//! after another.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bma_ts::Timestamp;
+//!
+//! let base = Timestamp::from_secs(1_713_027_747);
+//! let ts = Timestamp::from_millis(1_713_027_747_365);
+//! let mut buf = Vec::new();
+//! ts.encode_varint(&mut buf).unwrap();
+//! assert_eq!(buf.len(), 9);
+//! buf.clear();
+//! ts.encode_varint_relative(base, &mut buf).unwrap();
+//! assert_eq!(buf.len(), 5);
+//! let (decoded, len) = Timestamp::decode_varint_relative_slice(&buf, base).unwrap();
+//! assert_eq!((decoded, len), (ts, 5));
+//! ```
+use std::io::{self, Read, Write};
+
+use crate::{
+    Error, Monotonic, Timestamp,
+    common::{ZigZag, duration_from_nanos},
+};
+
+/// Maximum length of an encoded 128-bit value
+pub const MAX_LEN: usize = 19;
+
+/// Writes an unsigned value, returns the number of bytes written
+pub fn encode_varint<W: Write>(value: u128, writer: &mut W) -> Result<usize, Error> {
+    let mut buf = [0_u8; MAX_LEN];
+    let mut value = value;
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write_all(&buf[..len]).map_err(encode_error)?;
+    Ok(len)
+}
+
+/// Reads an unsigned value
+pub fn decode_varint<R: Read>(reader: &mut R) -> Result<u128, Error> {
+    let mut value: u128 = 0;
+    for i in 0..MAX_LEN {
+        let mut byte = [0_u8];
+        reader.read_exact(&mut byte).map_err(decode_error)?;
+        let bits = u128::from(byte[0] & 0x7f);
+        let shift = 7 * u32::try_from(i)?;
+        if shift == 126 && bits > 0b11 {
+            return Err(Error::Decode("varint overflow".to_owned()));
+        }
+        value |= bits << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::Decode(format!(
+        "varint longer than {} bytes",
+        MAX_LEN
+    )))
+}
+
+/// Writes a signed value (zigzag), returns the number of bytes written
+pub fn encode_varint_signed<W: Write>(value: i128, writer: &mut W) -> Result<usize, Error> {
+    encode_varint(value.zigzag(), writer)
+}
+
+/// Reads a signed value (zigzag)
+pub fn decode_varint_signed<R: Read>(reader: &mut R) -> Result<i128, Error> {
+    decode_varint(reader).map(i128::unzigzag)
+}
+
+/// Writes an unsigned value into the slice, returns the number of bytes written
+pub fn encode_varint_slice(value: u128, buf: &mut [u8]) -> Result<usize, Error> {
+    encode_varint(value, &mut &mut buf[..])
+}
+
+/// Reads an unsigned value from the slice, returns the value and the number of bytes read
+pub fn decode_varint_slice(buf: &[u8]) -> Result<(u128, usize), Error> {
+    let mut reader = buf;
+    let value = decode_varint(&mut reader)?;
+    Ok((value, buf.len() - reader.len()))
+}
+
+/// Writes a signed value (zigzag) into the slice, returns the number of bytes written
+pub fn encode_varint_signed_slice(value: i128, buf: &mut [u8]) -> Result<usize, Error> {
+    encode_varint_slice(value.zigzag(), buf)
+}
+
+/// Reads a signed value (zigzag) from the slice, returns the value and the number of bytes read
+pub fn decode_varint_signed_slice(buf: &[u8]) -> Result<(i128, usize), Error> {
+    decode_varint_slice(buf).map(|(v, len)| (i128::unzigzag(v), len))
+}
+
+fn encode_error(err: io::Error) -> Error {
+    match err.kind() {
+        io::ErrorKind::WriteZero => Error::Convert("varint buffer too small".to_owned()),
+        _ => Error::Convert(err.to_string()),
+    }
+}
+
+fn decode_error(err: io::Error) -> Error {
+    match err.kind() {
+        io::ErrorKind::UnexpectedEof => Error::Decode("varint truncated".to_owned()),
+        _ => Error::Decode(err.to_string()),
+    }
+}
+
+macro_rules! impl_varint {
+    ($t: ty) => {
+        impl $t {
+            /// Writes the value as LEB128 nanoseconds, returns the number of bytes written
+            pub fn encode_varint<W: Write>(self, writer: &mut W) -> Result<usize, Error> {
+                encode_varint(self.as_nanos(), writer)
+            }
+            /// Reads a value written with [`Self::encode_varint`]
+            pub fn decode_varint<R: Read>(reader: &mut R) -> Result<Self, Error> {
+                Ok(Self(duration_from_nanos(decode_varint(reader)?)?))
+            }
+            /// Writes the value into the slice, returns the number of bytes written
+            pub fn encode_varint_slice(self, buf: &mut [u8]) -> Result<usize, Error> {
+                encode_varint_slice(self.as_nanos(), buf)
+            }
+            /// Reads a value from the slice, returns the value and the number of bytes read
+            pub fn decode_varint_slice(buf: &[u8]) -> Result<(Self, usize), Error> {
+                let (nanos, len) = decode_varint_slice(buf)?;
+                Ok((Self(duration_from_nanos(nanos)?), len))
+            }
+            /// Writes the signed difference from the base as zigzag LEB128 nanoseconds, returns
+            /// the number of bytes written
+            pub fn encode_varint_relative<W: Write>(
+                self,
+                base: Self,
+                writer: &mut W,
+            ) -> Result<usize, Error> {
+                encode_varint_signed(self.nanos_since(base)?, writer)
+            }
+            /// Reads a value written with [`Self::encode_varint_relative`]
+            pub fn decode_varint_relative<R: Read>(
+                reader: &mut R,
+                base: Self,
+            ) -> Result<Self, Error> {
+                base.add_signed_nanos(decode_varint_signed(reader)?)
+            }
+            /// Writes the signed difference from the base into the slice, returns the number of
+            /// bytes written
+            pub fn encode_varint_relative_slice(
+                self,
+                base: Self,
+                buf: &mut [u8],
+            ) -> Result<usize, Error> {
+                encode_varint_signed_slice(self.nanos_since(base)?, buf)
+            }
+            /// Reads a base-relative value from the slice, returns the value and the number of
+            /// bytes read
+            pub fn decode_varint_relative_slice(
+                buf: &[u8],
+                base: Self,
+            ) -> Result<(Self, usize), Error> {
+                let (nanos, len) = decode_varint_signed_slice(buf)?;
+                Ok((base.add_signed_nanos(nanos)?, len))
+            }
+            fn nanos_since(self, base: Self) -> Result<i128, Error> {
+                Ok(i128::try_from(self.as_nanos())? - i128::try_from(base.as_nanos())?)
+            }
+            fn add_signed_nanos(self, nanos: i128) -> Result<Self, Error> {
+                i128::try_from(self.as_nanos())?
+                    .checked_add(nanos)
+                    .ok_or_else(|| Error::Decode("relative varint overflow".to_owned()))
+                    .and_then(|v| Ok(Self(duration_from_nanos(u128::try_from(v)?)?)))
+            }
+        }
+    };
+}
+
+impl_varint!(Timestamp);
+impl_varint!(Monotonic);
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_varint_signed_slice, decode_varint_slice, encode_varint_signed_slice};
+    use crate::{Error, Monotonic, Timestamp};
+
+    #[test]
+    fn test_varint() {
+        for (nanos, len) in [(0, 1), (127, 1), (128, 2), (1_713_027_747_365_000_001, 9)] {
+            let ts = Timestamp::from_nanos(nanos);
+            let mut buf = Vec::new();
+            assert_eq!(ts.encode_varint(&mut buf).unwrap(), len);
+            assert_eq!(Timestamp::decode_varint(&mut buf.as_slice()).unwrap(), ts);
+        }
+        let mono = Monotonic::from_secs(u64::MAX);
+        let mut buf = [0_u8; 32];
+        let len = mono.encode_varint_slice(&mut buf).unwrap();
+        assert_eq!(len, 14);
+        assert_eq!(Monotonic::decode_varint_slice(&buf).unwrap(), (mono, len));
+        assert_eq!(
+            decode_varint_slice(&[0xff; 18]),
+            Err(Error::Decode("varint truncated".to_owned()))
+        );
+        assert!(decode_varint_slice(&[0xff; 19]).is_err());
+        assert_eq!(
+            mono.encode_varint_slice(&mut buf[..13]),
+            Err(Error::Convert("varint buffer too small".to_owned()))
+        );
+        // values out of the Duration range
+        let mut buf = Vec::new();
+        super::encode_varint(u128::MAX, &mut buf).unwrap();
+        assert_eq!(decode_varint_slice(&buf).unwrap(), (u128::MAX, 19));
+        assert!(Timestamp::decode_varint_slice(&buf).is_err());
+    }
+
+    #[test]
+    fn test_varint_relative() {
+        let base = Timestamp::from_secs(1_713_027_747);
+        let mut buf = Vec::new();
+        for ts in [
+            Timestamp::from_nanos(1_713_027_746_999_999_999),
+            Timestamp::from_nanos(1_713_027_747_000_000_063),
+            Timestamp::from_secs(0),
+        ] {
+            buf.clear();
+            ts.encode_varint_relative(base, &mut buf).unwrap();
+            assert_eq!(
+                Timestamp::decode_varint_relative(&mut buf.as_slice(), base).unwrap(),
+                ts
+            );
+        }
+        let mut buf = [0_u8; 4];
+        let len = encode_varint_signed_slice(-1_000, &mut buf).unwrap();
+        assert_eq!(decode_varint_signed_slice(&buf).unwrap(), (-1_000, len));
+        assert!(Timestamp::decode_varint_relative_slice(&buf, Timestamp::from_nanos(999)).is_err());
+        assert_eq!(
+            Monotonic::decode_varint_relative_slice(&buf, Monotonic::from_micros(1)).unwrap(),
+            (Monotonic::from_nanos(0), len)
+        );
+    }
+}